@@ -4,5 +4,5 @@ fn main() {
     println!("Select a race!");
     let race = o5e::race::Race::Human;
 
-    println!("You have chosen to become a {}", race.to_string());
+    println!("You have chosen to become a {}", race);
 }
@@ -0,0 +1,56 @@
+use crate::Criterion;
+
+/// A single tier of an achievement, e.g. the "Silver" tier of "Gold Hoarder"
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AchievementTier {
+    pub name: String,
+    pub criterion: Criterion,
+}
+
+/// An achievement with one or more tiers, tiers unlock in order
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Hidden achievements are not listed until their first tier is unlocked
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hidden: bool,
+    pub tiers: Vec<AchievementTier>,
+}
+
+impl Achievement {
+    /// An achievement with a single tier
+    pub fn new(id: &str, name: &str, description: &str, criterion: Criterion) -> Self {
+        Self::tiered(
+            id,
+            name,
+            description,
+            vec![AchievementTier {
+                name: name.to_string(),
+                criterion,
+            }],
+        )
+    }
+
+    pub fn tiered(id: &str, name: &str, description: &str, tiers: Vec<AchievementTier>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            hidden: false,
+            tiers,
+        }
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    pub fn is_tiered(&self) -> bool {
+        self.tiers.len() > 1
+    }
+}
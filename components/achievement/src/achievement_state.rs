@@ -0,0 +1,111 @@
+use overworld_incremental::counter::CountingObject;
+use overworld_progression::experience::ExperienceSystem;
+use overworld_resource::resource::{GameResource, Resource};
+use std::collections::BTreeMap;
+
+/// A snapshot of the game state achievements are evaluated against
+///
+/// Levels, resources and workers are recorded from overworld's own types,
+/// counters are free-form and incremented by the game (e.g. "rps_wins").
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AchievementState {
+    levels: BTreeMap<String, u64>,
+    resources: BTreeMap<String, u64>,
+    workers: BTreeMap<String, u64>,
+    counters: BTreeMap<String, u64>,
+}
+
+impl AchievementState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the level of an experience system under `name`
+    pub fn record_level(&mut self, name: &str, system: &ExperienceSystem) {
+        self.levels.insert(name.to_string(), system.get_level());
+    }
+
+    /// Records the value of a resource under its own name
+    pub fn record_resource(&mut self, resource: &GameResource) {
+        self.resources.insert(resource.name(), resource.value());
+    }
+
+    /// Records the worker count of a counting object under its own name
+    pub fn record_workers(&mut self, counter: &mut CountingObject) {
+        let workers = counter.workers();
+        self.workers.insert(counter.get_name().to_string(), workers);
+    }
+
+    /// Increments a custom counter, returns the new value
+    pub fn increment(&mut self, counter: &str, by: u64) -> u64 {
+        let value = self.counters.entry(counter.to_string()).or_insert(0);
+        *value = value.saturating_add(by);
+
+        *value
+    }
+
+    pub fn set_counter(&mut self, counter: &str, value: u64) {
+        self.counters.insert(counter.to_string(), value);
+    }
+
+    pub fn level(&self, name: &str) -> u64 {
+        self.levels.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn resource(&self, name: &str) -> u64 {
+        self.resources.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn workers(&self, name: &str) -> u64 {
+        self.workers.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AchievementState;
+    use overworld_incremental::counter::CountingObject;
+    use overworld_progression::experience::ExperienceSystem;
+    use overworld_resource::resource::{GameResource, Resource};
+    use std::sync::atomic::AtomicU64;
+
+    #[test]
+    fn test_record() {
+        let mut state = AchievementState::new();
+
+        state.record_level("player", &ExperienceSystem::simple(100, vec![100, 250]));
+        state.record_resource(&GameResource::new("Gold".to_string(), 500));
+
+        let mut counter = CountingObject::new(
+            String::from("Farm"),
+            AtomicU64::new(1),
+            AtomicU64::new(10),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+        );
+        counter.perform_buy(10);
+        state.record_workers(&mut counter);
+
+        assert_eq!(2, state.level("player"));
+        assert_eq!(500, state.resource("Gold"));
+        assert_eq!(1, state.workers("Farm"));
+        assert_eq!(0, state.workers("Mine"));
+    }
+
+    #[test]
+    fn test_counters() {
+        let mut state = AchievementState::new();
+
+        assert_eq!(1, state.increment("rps_wins", 1));
+        assert_eq!(3, state.increment("rps_wins", 2));
+
+        state.set_counter("deaths", 10);
+        assert_eq!(10, state.counter("deaths"));
+        assert_eq!(0, state.counter("unknown"));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{Achievement, AchievementState, CriterionProgress};
+use std::collections::BTreeMap;
+
+/// A tier of an achievement that was unlocked by an evaluation
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AchievementUnlock {
+    pub achievement: String,
+    /// The index of the tier that was unlocked, starting at 0
+    pub tier: usize,
+    pub tier_name: String,
+}
+
+/// Tracks which achievements (and tiers) have been unlocked
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AchievementTracker {
+    achievements: Vec<Achievement>,
+    state: AchievementState,
+    /// Number of tiers unlocked per achievement
+    unlocked: BTreeMap<String, usize>,
+}
+
+impl AchievementTracker {
+    pub fn new(achievements: Vec<Achievement>) -> Self {
+        Self {
+            achievements,
+            ..Self::default()
+        }
+    }
+
+    pub fn add(&mut self, achievement: Achievement) {
+        self.achievements.push(achievement);
+    }
+
+    pub fn state(&self) -> &AchievementState {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut AchievementState {
+        &mut self.state
+    }
+
+    /// Evaluates every achievement against the state, returns the tiers that were newly unlocked
+    pub fn evaluate(&mut self) -> Vec<AchievementUnlock> {
+        let mut unlocks = vec![];
+
+        for achievement in &self.achievements {
+            let unlocked = self.unlocked.entry(achievement.id.clone()).or_insert(0);
+
+            while let Some(tier) = achievement.tiers.get(*unlocked) {
+                if !tier.criterion.is_met(&self.state) {
+                    break;
+                }
+
+                unlocks.push(AchievementUnlock {
+                    achievement: achievement.id.clone(),
+                    tier: *unlocked,
+                    tier_name: tier.name.clone(),
+                });
+                *unlocked += 1;
+            }
+        }
+
+        unlocks
+    }
+
+    /// Number of tiers unlocked for the achievement
+    pub fn unlocked_tiers(&self, id: &str) -> usize {
+        self.unlocked.get(id).copied().unwrap_or(0)
+    }
+
+    /// Whether every tier of the achievement has been unlocked
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.achievement(id)
+            .is_some_and(|achievement| self.unlocked_tiers(id) >= achievement.tiers.len())
+    }
+
+    pub fn achievement(&self, id: &str) -> Option<&Achievement> {
+        self.achievements
+            .iter()
+            .find(|achievement| achievement.id == id)
+    }
+
+    /// Achievements that are not hidden, or hidden but already (partially) unlocked
+    pub fn visible(&self) -> impl Iterator<Item = &Achievement> {
+        self.achievements
+            .iter()
+            .filter(|achievement| !achievement.hidden || self.unlocked_tiers(&achievement.id) > 0)
+    }
+
+    /// Progress towards the next locked tier, None if unknown or completed
+    pub fn progress(&self, id: &str) -> Option<CriterionProgress> {
+        let achievement = self.achievement(id)?;
+
+        achievement
+            .tiers
+            .get(self.unlocked_tiers(id))
+            .map(|tier| tier.criterion.progress(&self.state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Achievement, AchievementTier, AchievementTracker, Criterion};
+    use overworld_progression::experience::ExperienceSystem;
+    use overworld_resource::resource::{GameResource, Resource};
+
+    fn new_tracker() -> AchievementTracker {
+        AchievementTracker::new(vec![
+            Achievement::new(
+                "level_10",
+                "Seasoned",
+                "Reach level 10",
+                Criterion::Level {
+                    system: String::from("player"),
+                    level: 10,
+                },
+            ),
+            Achievement::tiered(
+                "gold",
+                "Gold Hoarder",
+                "Collect gold",
+                vec![
+                    AchievementTier {
+                        name: String::from("Bronze"),
+                        criterion: Criterion::Resource {
+                            resource: String::from("Gold"),
+                            amount: 1_000,
+                        },
+                    },
+                    AchievementTier {
+                        name: String::from("Gold"),
+                        criterion: Criterion::Resource {
+                            resource: String::from("Gold"),
+                            amount: 1_000_000,
+                        },
+                    },
+                ],
+            ),
+            Achievement::new(
+                "rps",
+                "Rock Solid",
+                "Win 50 rock-paper-scissors rounds",
+                Criterion::Counter {
+                    counter: String::from("rps_wins"),
+                    amount: 50,
+                },
+            )
+            .hidden(),
+        ])
+    }
+
+    #[test]
+    fn test_evaluate_level() {
+        let mut tracker = new_tracker();
+        let mut system = ExperienceSystem::simple(0, (1..10).map(|level| level * 100).collect());
+
+        tracker.state_mut().record_level("player", &system);
+        assert!(tracker.evaluate().is_empty());
+
+        system.add_experience(900);
+        tracker.state_mut().record_level("player", &system);
+
+        let unlocks = tracker.evaluate();
+        assert_eq!(1, unlocks.len());
+        assert_eq!("level_10", unlocks[0].achievement);
+
+        assert!(tracker.evaluate().is_empty());
+        assert!(tracker.is_completed("level_10"));
+    }
+
+    #[test]
+    fn test_tiered() {
+        let mut tracker = new_tracker();
+
+        tracker
+            .state_mut()
+            .record_resource(&GameResource::new("Gold".to_string(), 1_500));
+        let unlocks = tracker.evaluate();
+        assert_eq!(1, unlocks.len());
+        assert_eq!("Bronze", unlocks[0].tier_name);
+        assert!(!tracker.is_completed("gold"));
+        assert_eq!(1_500, tracker.progress("gold").unwrap().current);
+
+        tracker
+            .state_mut()
+            .record_resource(&GameResource::new("Gold".to_string(), 1_000_000));
+        let unlocks = tracker.evaluate();
+        assert_eq!(1, unlocks[0].tier);
+        assert!(tracker.is_completed("gold"));
+        assert!(tracker.progress("gold").is_none());
+    }
+
+    #[test]
+    fn test_hidden() {
+        let mut tracker = new_tracker();
+
+        assert_eq!(2, tracker.visible().count());
+
+        tracker.state_mut().increment("rps_wins", 50);
+        tracker.evaluate();
+
+        assert_eq!(3, tracker.visible().count());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::AchievementState;
+
+/// How close a criterion is to being met
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriterionProgress {
+    pub current: u64,
+    pub target: u64,
+}
+
+impl CriterionProgress {
+    pub fn is_met(&self) -> bool {
+        self.current >= self.target
+    }
+
+    /// Progress as a percentage, capped at 100
+    pub fn percentage(&self) -> f64 {
+        if self.target == 0 {
+            return 100.;
+        }
+
+        (self.current.min(self.target) as f64 / self.target as f64) * 100.
+    }
+}
+
+/// A condition an achievement requires, evaluated against an AchievementState
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Criterion {
+    /// The recorded experience system has reached the level
+    Level { system: String, level: u64 },
+    /// The recorded resource holds at least the amount
+    Resource { resource: String, amount: u64 },
+    /// The recorded counting object has at least the amount of workers
+    Workers { counter: String, workers: u64 },
+    /// The custom counter has reached the amount
+    Counter { counter: String, amount: u64 },
+    /// Every criterion must be met
+    All(Vec<Criterion>),
+}
+
+impl Criterion {
+    pub fn progress(&self, state: &AchievementState) -> CriterionProgress {
+        let (current, target) = match self {
+            Criterion::Level { system, level } => (state.level(system), *level),
+            Criterion::Resource { resource, amount } => (state.resource(resource), *amount),
+            Criterion::Workers { counter, workers } => (state.workers(counter), *workers),
+            Criterion::Counter { counter, amount } => (state.counter(counter), *amount),
+            Criterion::All(criteria) => (
+                criteria
+                    .iter()
+                    .filter(|criterion| criterion.is_met(state))
+                    .count() as u64,
+                criteria.len() as u64,
+            ),
+        };
+
+        CriterionProgress { current, target }
+    }
+
+    pub fn is_met(&self, state: &AchievementState) -> bool {
+        self.progress(state).is_met()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AchievementState, Criterion};
+
+    #[test]
+    fn test_counter_criterion() {
+        let mut state = AchievementState::new();
+        let criterion = Criterion::Counter {
+            counter: String::from("rps_wins"),
+            amount: 50,
+        };
+
+        state.increment("rps_wins", 25);
+        let progress = criterion.progress(&state);
+        assert!(!progress.is_met());
+        assert_eq!(50., progress.percentage());
+
+        state.increment("rps_wins", 25);
+        assert!(criterion.is_met(&state));
+    }
+
+    #[test]
+    fn test_all_criterion() {
+        let mut state = AchievementState::new();
+        let criterion = Criterion::All(vec![
+            Criterion::Counter {
+                counter: String::from("a"),
+                amount: 1,
+            },
+            Criterion::Counter {
+                counter: String::from("b"),
+                amount: 1,
+            },
+        ]);
+
+        state.increment("a", 1);
+        assert_eq!(1, criterion.progress(&state).current);
+        assert!(!criterion.is_met(&state));
+
+        state.increment("b", 1);
+        assert!(criterion.is_met(&state));
+    }
+}
@@ -0,0 +1,9 @@
+mod achievement;
+mod achievement_state;
+mod achievement_tracker;
+mod criterion;
+
+pub use achievement::{Achievement, AchievementTier};
+pub use achievement_state::AchievementState;
+pub use achievement_tracker::{AchievementTracker, AchievementUnlock};
+pub use criterion::{Criterion, CriterionProgress};
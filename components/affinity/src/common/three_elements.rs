@@ -19,7 +19,7 @@ impl Affinity for Element {
     }
 
     fn strong_against(&self, other: &Self) -> bool {
-        if self.eq(&other) {
+        if self.eq(other) {
             return false;
         }
 
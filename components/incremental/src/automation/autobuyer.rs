@@ -0,0 +1,435 @@
+use crate::counter::{BuyQuantity, CountingObject};
+use overworld_resource::resource::{GameResource, Resource};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AutomationError {
+    /// No rule exists for this counter
+    UnknownRule(String),
+    /// A rule for this counter already exists
+    DuplicateRule(String),
+}
+
+impl Display for AutomationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AutomationError::UnknownRule(name) => write!(f, "No rule for counter '{}'", name),
+            AutomationError::DuplicateRule(name) => {
+                write!(f, "A rule for counter '{}' already exists", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AutomationError {}
+
+/// When and how an Autobuyer buys workers of a single counter
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutobuyRule {
+    counter: String,
+    interval: Duration,
+    max_cost_percentage: u64,
+    reserve: u64,
+    priority: i64,
+    max_purchases: u64,
+    enabled: bool,
+    /// Time since the rule last triggered
+    elapsed: Duration,
+}
+
+impl AutobuyRule {
+    /// Buys a worker every `interval` whenever it is affordable,
+    /// a zero interval triggers on every call to `Autobuyer::run`
+    pub fn new(counter: &str, interval: Duration) -> Self {
+        Self {
+            counter: counter.to_string(),
+            interval,
+            max_cost_percentage: 100,
+            reserve: 0,
+            priority: 0,
+            max_purchases: 1,
+            enabled: true,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Spend at most this percentage of the wallet each time the rule runs, defaults to 100
+    pub fn with_max_cost_percentage(mut self, percentage: u64) -> Self {
+        self.max_cost_percentage = percentage;
+        self
+    }
+
+    /// Never spend the wallet below this amount
+    pub fn with_reserve(mut self, reserve: u64) -> Self {
+        self.reserve = reserve;
+        self
+    }
+
+    /// Rules with a higher priority buy first, rules with equal priority in the order they were added
+    pub fn with_priority(mut self, priority: i64) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The most workers bought every time the rule triggers, defaults to 1
+    pub fn with_max_purchases(mut self, max_purchases: u64) -> Self {
+        self.max_purchases = max_purchases;
+        self
+    }
+
+    pub fn counter(&self) -> &str {
+        &self.counter
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn max_cost_percentage(&self) -> u64 {
+        self.max_cost_percentage
+    }
+
+    pub fn reserve(&self) -> u64 {
+        self.reserve
+    }
+
+    pub fn priority(&self) -> i64 {
+        self.priority
+    }
+
+    pub fn max_purchases(&self) -> u64 {
+        self.max_purchases
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Advances the timer, returns how many times the rule triggers
+    fn triggers(&mut self, delta: Duration) -> u64 {
+        if self.interval.is_zero() {
+            return 1;
+        }
+
+        self.elapsed += delta;
+        let triggers = self.elapsed.as_nanos() / self.interval.as_nanos();
+        self.elapsed = Duration::from_nanos(
+            (self.elapsed.as_nanos() % self.interval.as_nanos()).min(u64::MAX as u128) as u64,
+        );
+
+        triggers.min(u64::MAX as u128) as u64
+    }
+
+    /// The most that may be spent from `wallet` each time the rule runs
+    fn budget(&self, wallet: u64) -> u64 {
+        let share = wallet as u128 * self.max_cost_percentage as u128 / 100;
+
+        wallet
+            .saturating_sub(self.reserve)
+            .min(share.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// Workers an Autobuyer bought for a counter during a single run
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoPurchase {
+    pub counter: String,
+    pub quantity: u64,
+    pub spent: u64,
+}
+
+/// Buys workers automatically according to a set of rules, e.g. for unlockable autobuyers
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Autobuyer {
+    rules: Vec<AutobuyRule>,
+    enabled: bool,
+}
+
+impl Default for Autobuyer {
+    fn default() -> Self {
+        Self {
+            rules: vec![],
+            enabled: true,
+        }
+    }
+}
+
+impl Autobuyer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, rule: AutobuyRule) -> Result<(), AutomationError> {
+        if self.rule(&rule.counter).is_some() {
+            return Err(AutomationError::DuplicateRule(rule.counter));
+        }
+
+        // Stable, so rules with equal priority keep the order they were added in
+        let index = self
+            .rules
+            .iter()
+            .position(|existing| existing.priority < rule.priority)
+            .unwrap_or(self.rules.len());
+        self.rules.insert(index, rule);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, counter: &str) -> Result<AutobuyRule, AutomationError> {
+        let index = self
+            .rules
+            .iter()
+            .position(|rule| rule.counter == counter)
+            .ok_or_else(|| AutomationError::UnknownRule(counter.to_string()))?;
+
+        Ok(self.rules.remove(index))
+    }
+
+    pub fn rule(&self, counter: &str) -> Option<&AutobuyRule> {
+        self.rules.iter().find(|rule| rule.counter == counter)
+    }
+
+    /// Rules in the order they buy in
+    pub fn rules(&self) -> &[AutobuyRule] {
+        &self.rules
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Toggles every rule at once, e.g. for a master switch
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Toggles a single rule, a disabled rule does not keep time
+    pub fn set_rule_enabled(
+        &mut self,
+        counter: &str,
+        enabled: bool,
+    ) -> Result<(), AutomationError> {
+        let rule = self
+            .rules
+            .iter_mut()
+            .find(|rule| rule.counter == counter)
+            .ok_or_else(|| AutomationError::UnknownRule(counter.to_string()))?;
+
+        rule.enabled = enabled;
+        if !enabled {
+            rule.elapsed = Duration::ZERO;
+        }
+
+        Ok(())
+    }
+
+    /// Advances the timers and buys workers from `wallet` for every rule that triggers
+    ///
+    /// The number of workers is calculated once per rule from its budget, and bought in a single
+    /// transaction, see `CountingObject::buy_with`. Rules are run in priority order, so higher priorities get the first claim on the wallet.
+    /// Rules for counters that are not given are skipped.
+    pub fn run<'a>(
+        &mut self,
+        delta: Duration,
+        counters: impl IntoIterator<Item = &'a mut CountingObject>,
+        wallet: &GameResource,
+    ) -> Vec<AutoPurchase> {
+        if !self.enabled {
+            return vec![];
+        }
+
+        let mut counters: BTreeMap<String, &mut CountingObject> = counters
+            .into_iter()
+            .map(|counter| (counter.get_name().to_string(), counter))
+            .collect();
+        let mut purchases = vec![];
+
+        for rule in self.rules.iter_mut().filter(|rule| rule.enabled) {
+            let triggers = rule.triggers(delta);
+            let Some(counter) = counters.get_mut(&rule.counter) else {
+                continue;
+            };
+
+            let limit = triggers.saturating_mul(rule.max_purchases);
+            let quantity = counter
+                .max_affordable(rule.budget(wallet.value()))
+                .min(limit);
+            if quantity == 0 {
+                continue;
+            }
+
+            if let Ok(transaction) = counter.buy_with(wallet, BuyQuantity::Exact(quantity)) {
+                purchases.push(AutoPurchase {
+                    counter: rule.counter.clone(),
+                    quantity: transaction.quantity,
+                    spent: transaction.amount,
+                });
+            }
+        }
+
+        purchases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automation::{AutoPurchase, AutobuyRule, Autobuyer, AutomationError};
+    use crate::counter::{CostCurve, CountingObject};
+    use overworld_resource::resource::{GameResource, Resource};
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    fn new_counting_object(name: &str, initial_cost: u64) -> CountingObject {
+        CountingObject::from(
+            String::from(name),
+            AtomicU64::new(1),
+            AtomicU64::new(1),
+            AtomicU64::new(100),
+            AtomicU64::new(initial_cost),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+        )
+    }
+
+    fn wallet(value: u64) -> GameResource {
+        GameResource::new(String::from("gold"), value)
+    }
+
+    #[test]
+    fn test_intervals() {
+        let mut autobuyer = Autobuyer::new();
+        autobuyer
+            .add(AutobuyRule::new("miner", Duration::from_secs(10)))
+            .unwrap();
+
+        let mut miner = new_counting_object("miner", 10);
+        let gold = wallet(1000);
+
+        assert!(autobuyer
+            .run(Duration::from_secs(9), [&mut miner], &gold)
+            .is_empty());
+
+        // 9s + 21s covers three intervals, costing 10, 20 and 30
+        let purchases = autobuyer.run(Duration::from_secs(21), [&mut miner], &gold);
+        assert_eq!(
+            vec![AutoPurchase {
+                counter: String::from("miner"),
+                quantity: 3,
+                spent: 60,
+            }],
+            purchases
+        );
+        assert_eq!(4, miner.workers());
+        assert_eq!(940, gold.value());
+    }
+
+    #[test]
+    fn test_reserve_and_percentage() {
+        let mut autobuyer = Autobuyer::new();
+        autobuyer
+            .add(
+                AutobuyRule::new("miner", Duration::ZERO)
+                    .with_reserve(50)
+                    .with_max_purchases(u64::MAX),
+            )
+            .unwrap();
+        autobuyer
+            .add(
+                AutobuyRule::new("farm", Duration::ZERO)
+                    .with_max_cost_percentage(10)
+                    .with_max_purchases(u64::MAX),
+            )
+            .unwrap();
+
+        let mut miner = new_counting_object("miner", 10);
+        let mut farm = new_counting_object("farm", 10);
+        let gold = wallet(100);
+
+        let purchases = autobuyer.run(Duration::ZERO, [&mut miner, &mut farm], &gold);
+
+        // miner: 10 + 20 leaves 70, 30 would break the reserve of 50
+        // farm: 10 is 14% of 70
+        assert_eq!(1, purchases.len());
+        assert_eq!(3, miner.workers());
+        assert_eq!(1, farm.workers());
+        assert_eq!(70, gold.value());
+    }
+
+    #[test]
+    fn test_free_counters() {
+        let mut autobuyer = Autobuyer::new();
+        autobuyer
+            .add(AutobuyRule::new("miner", Duration::ZERO).with_max_purchases(u64::MAX))
+            .unwrap();
+
+        let mut miner = new_counting_object("miner", 0)
+            .with_cost_curve(CostCurve::Exponential { percentage: 100 });
+        let gold = wallet(100);
+
+        let purchases = autobuyer.run(Duration::ZERO, [&mut miner], &gold);
+        assert_eq!(u64::MAX - 1, purchases[0].quantity);
+        assert_eq!(u64::MAX, miner.workers());
+        assert_eq!(100, gold.value());
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut autobuyer = Autobuyer::new();
+        autobuyer
+            .add(AutobuyRule::new("miner", Duration::ZERO))
+            .unwrap();
+        autobuyer
+            .add(AutobuyRule::new("farm", Duration::ZERO).with_priority(1))
+            .unwrap();
+
+        let mut miner = new_counting_object("miner", 10);
+        let mut farm = new_counting_object("farm", 10);
+        let gold = wallet(10);
+
+        autobuyer.run(Duration::ZERO, [&mut miner, &mut farm], &gold);
+        assert_eq!("farm", autobuyer.rules()[0].counter());
+        assert_eq!(2, farm.workers());
+        assert_eq!(1, miner.workers());
+    }
+
+    #[test]
+    fn test_toggles() {
+        let mut autobuyer = Autobuyer::new();
+        autobuyer
+            .add(AutobuyRule::new("miner", Duration::from_secs(1)))
+            .unwrap();
+        assert_eq!(
+            Err(AutomationError::DuplicateRule(String::from("miner"))),
+            autobuyer.add(AutobuyRule::new("miner", Duration::ZERO))
+        );
+
+        let mut miner = new_counting_object("miner", 10);
+        let gold = wallet(1000);
+
+        autobuyer.set_enabled(false);
+        assert!(autobuyer
+            .run(Duration::from_secs(5), [&mut miner], &gold)
+            .is_empty());
+
+        autobuyer.set_enabled(true);
+        autobuyer.set_rule_enabled("miner", false).unwrap();
+        assert!(autobuyer
+            .run(Duration::from_secs(5), [&mut miner], &gold)
+            .is_empty());
+        assert_eq!(
+            Err(AutomationError::UnknownRule(String::from("farm"))),
+            autobuyer.set_rule_enabled("farm", true)
+        );
+
+        autobuyer.set_rule_enabled("miner", true).unwrap();
+        assert_eq!(
+            1,
+            autobuyer.run(Duration::from_secs(1), [&mut miner], &gold)[0].quantity
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod autobuyer;
+
+pub use autobuyer::{AutoPurchase, AutobuyRule, Autobuyer, AutomationError};
@@ -0,0 +1,9 @@
+/// How many workers to buy or sell at once, e.g. for "x10", "x100" and "max" buttons
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuyQuantity {
+    /// Exactly this many, the transaction fails if that is not possible
+    Exact(u64),
+    /// As many as possible: as many as can be afforded, or every worker when selling
+    Max,
+}
@@ -0,0 +1,451 @@
+#[cfg(feature = "big_number")]
+use crate::number::BigNumber;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// The most purchases `CostCurve::max_affordable` considers for curves without a closed form
+pub const MAX_ITERATED: u64 = 1_000_000;
+
+/// How the cost of a CountingObject grows with every worker bought
+///
+/// Costs saturate at u64::MAX instead of overflowing.
+#[derive(Clone, Default)]
+pub enum CostCurve {
+    /// `initial_cost * workers * buy_cost / 100`, where the first purchase costs `initial_cost`
+    #[default]
+    Linear,
+    /// `initial_cost * (percentage / 100) ^ workers`, e.g. 115 for the classic 1.15 growth
+    Exponential { percentage: u64 },
+    /// `initial_cost * (workers + 1) ^ exponent`
+    Polynomial { exponent: u32 },
+    /// `initial_cost * (percentage / 100) ^ (workers / every)`, the cost rises every `every` workers
+    Stepped { every: u64, percentage: u64 },
+    /// Calculates the cost from the initial cost and the number of workers owned
+    Custom(Arc<dyn Fn(u64, u64) -> u64 + Send + Sync>),
+}
+
+impl CostCurve {
+    /// Creates a custom curve from a function of (initial_cost, workers)
+    pub fn custom(curve: impl Fn(u64, u64) -> u64 + Send + Sync + 'static) -> Self {
+        CostCurve::Custom(Arc::new(curve))
+    }
+
+    /// The cost of the next worker when `workers` are owned,
+    /// `buy_cost` is the percentage used by the linear curve
+    pub fn cost(&self, initial_cost: u64, buy_cost: u64, workers: u64) -> u64 {
+        match self {
+            CostCurve::Linear => {
+                if workers == 0 {
+                    return initial_cost;
+                }
+
+                saturate(initial_cost as u128 * workers as u128 * buy_cost as u128 / 100)
+            }
+            CostCurve::Exponential { percentage } => grow(initial_cost, *percentage, workers),
+            CostCurve::Polynomial { exponent } => saturate(
+                (workers as u128 + 1)
+                    .checked_pow(*exponent)
+                    .map_or(u128::MAX, |factor| {
+                        factor.saturating_mul(initial_cost as u128)
+                    }),
+            ),
+            CostCurve::Stepped { every, percentage } => {
+                grow(initial_cost, *percentage, workers / (*every).max(1))
+            }
+            CostCurve::Custom(curve) => curve(initial_cost, workers),
+        }
+    }
+}
+
+impl CostCurve {
+    /// Whether `total_cost` prices any quantity, instead of at most MAX_ITERATED purchases
+    pub fn is_closed_form(&self) -> bool {
+        self.is_constant() || matches!(self, CostCurve::Linear | CostCurve::Exponential { .. })
+    }
+
+    /// Whether every worker costs the initial cost
+    fn is_constant(&self) -> bool {
+        matches!(
+            self,
+            CostCurve::Exponential { percentage: 100 }
+                | CostCurve::Polynomial { exponent: 0 }
+                | CostCurve::Stepped {
+                    percentage: 100,
+                    ..
+                }
+        )
+    }
+
+    /// The combined cost of the next `quantity` workers when `workers` are owned,
+    /// saturating at u64::MAX, see `checked_total_cost`
+    pub fn total_cost(&self, initial_cost: u64, buy_cost: u64, workers: u64, quantity: u64) -> u64 {
+        self.checked_total_cost(initial_cost, buy_cost, workers, quantity)
+            .unwrap_or(u64::MAX)
+    }
+
+    /// The combined cost of the next `quantity` workers when `workers` are owned,
+    /// None if it does not fit in a u64
+    ///
+    /// Up to MAX_ITERATED purchases this is exactly the sum of buying one at a time.
+    /// Beyond that the linear curve is still exact, the exponential curve uses the geometric
+    /// series rounded once, and curves without a closed form are None.
+    pub fn checked_total_cost(
+        &self,
+        initial_cost: u64,
+        buy_cost: u64,
+        workers: u64,
+        quantity: u64,
+    ) -> Option<u64> {
+        if quantity == 0 {
+            return Some(0);
+        }
+
+        if self.is_constant() {
+            return fit(initial_cost as u128 * quantity as u128);
+        }
+
+        match self {
+            CostCurve::Linear => {
+                let mut total = 0u128;
+                let mut first = workers;
+                let mut quantity = quantity;
+
+                if workers == 0 {
+                    total = initial_cost as u128;
+                    first = 1;
+                    quantity -= 1;
+                }
+
+                let factor = initial_cost as u128 * buy_cost as u128;
+                let end = first as u128 + quantity as u128;
+                let sum = floor_sum(factor, end);
+                if sum == u128::MAX {
+                    return None;
+                }
+
+                fit(total.saturating_add(sum - floor_sum(factor, first as u128)))
+            }
+            CostCurve::Exponential { percentage } if quantity > MAX_ITERATED => {
+                let ratio = *percentage as f64 / 100.;
+                let first = initial_cost as f64 * ratio.powf(workers as f64);
+                let total = (first * (ratio.powf(quantity as f64) - 1.) / (ratio - 1.)).round();
+
+                // u64::MAX as f64 rounds up to 2^64, which does not fit
+                (total.is_finite() && total < u64::MAX as f64).then_some(total as u64)
+            }
+            _ if quantity > MAX_ITERATED => None,
+            _ => (workers..workers.checked_add(quantity)?).try_fold(0u64, |total, owned| {
+                total.checked_add(self.cost(initial_cost, buy_cost, owned))
+            }),
+        }
+    }
+
+    /// The most workers that can be bought with `money` when `workers` are owned
+    ///
+    /// Curves without a closed form consider at most MAX_ITERATED purchases.
+    pub fn max_affordable(
+        &self,
+        initial_cost: u64,
+        buy_cost: u64,
+        workers: u64,
+        money: u64,
+    ) -> u64 {
+        let available = u64::MAX - workers;
+
+        if !self.is_closed_form() {
+            let mut total = Some(0u64);
+
+            return (workers..workers.saturating_add(available.min(MAX_ITERATED)))
+                .take_while(|owned| {
+                    total = total.and_then(|total| {
+                        total.checked_add(self.cost(initial_cost, buy_cost, *owned))
+                    });
+                    total.is_some_and(|total| total <= money)
+                })
+                .count() as u64;
+        }
+
+        let affordable = |quantity| {
+            self.checked_total_cost(initial_cost, buy_cost, workers, quantity)
+                .is_some_and(|cost| cost <= money)
+        };
+
+        // Double until unaffordable, then binary search between the last two bounds
+        let mut low = 0;
+        let mut high = 1;
+        while affordable(high) {
+            if high == available {
+                return available;
+            }
+
+            low = high;
+            high = high.saturating_mul(2).min(available);
+        }
+
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+
+            if affordable(middle) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+}
+
+#[cfg(feature = "big_number")]
+impl CostCurve {
+    /// The cost of the next worker as a BigNumber, without saturating at u64::MAX
+    ///
+    /// Costs that fit in a u64 are exactly `cost`, only larger costs are calculated
+    /// as BigNumber. Custom curves are still limited to u64.
+    pub fn big_cost(&self, initial_cost: u64, buy_cost: u64, workers: u64) -> BigNumber {
+        let cost = self.cost(initial_cost, buy_cost, workers);
+        if cost < u64::MAX {
+            return BigNumber::from(cost);
+        }
+
+        let initial = BigNumber::from(initial_cost);
+
+        match self {
+            CostCurve::Linear => {
+                if workers == 0 {
+                    return initial;
+                }
+
+                initial * BigNumber::from(workers) * BigNumber::new(buy_cost as f64 / 100., 0)
+            }
+            CostCurve::Exponential { percentage } => {
+                initial * BigNumber::new(*percentage as f64 / 100., 0).pow(workers as f64)
+            }
+            CostCurve::Polynomial { exponent } => {
+                initial * BigNumber::from(workers.saturating_add(1)).pow(*exponent as f64)
+            }
+            CostCurve::Stepped { every, percentage } => {
+                let steps = workers / (*every).max(1);
+                initial * BigNumber::new(*percentage as f64 / 100., 0).pow(steps as f64)
+            }
+            CostCurve::Custom(_) => BigNumber::from(cost),
+        }
+    }
+}
+
+impl Debug for CostCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CostCurve::Linear => write!(f, "Linear"),
+            CostCurve::Exponential { percentage } => f
+                .debug_struct("Exponential")
+                .field("percentage", percentage)
+                .finish(),
+            CostCurve::Polynomial { exponent } => f
+                .debug_struct("Polynomial")
+                .field("exponent", exponent)
+                .finish(),
+            CostCurve::Stepped { every, percentage } => f
+                .debug_struct("Stepped")
+                .field("every", every)
+                .field("percentage", percentage)
+                .finish(),
+            CostCurve::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// `amount * (percentage / 100) ^ times`
+fn grow(amount: u64, percentage: u64, times: u64) -> u64 {
+    let factor = (percentage as f64 / 100.).powf(times as f64);
+
+    // float to int casts saturate
+    (amount as f64 * factor).round() as u64
+}
+
+/// The sum of `floor(factor * k / 100)` for every k in 0..count, saturating at u128::MAX
+fn floor_sum(factor: u128, count: u128) -> u128 {
+    // floor(factor * k / 100) = quotient * k + floor(remainder * k / 100),
+    // and the second term grows by exactly `remainder` every 100 values of k
+    let quotient = factor / 100;
+    let remainder = factor % 100;
+    let periods = count / 100;
+    let rest = count % 100;
+
+    let triangle = count.saturating_mul(count.saturating_sub(1)) / 2;
+    let period_sum: u128 = (0..100).map(|k| remainder * k / 100).sum();
+    let rest_sum: u128 = (0..rest).map(|k| remainder * k / 100).sum();
+
+    quotient
+        .saturating_mul(triangle)
+        .saturating_add(periods.saturating_mul(period_sum))
+        .saturating_add(
+            remainder
+                .saturating_mul(100)
+                .saturating_mul(periods.saturating_mul(periods.saturating_sub(1)) / 2),
+        )
+        .saturating_add(rest.saturating_mul(remainder).saturating_mul(periods))
+        .saturating_add(rest_sum)
+}
+
+fn fit(value: u128) -> Option<u64> {
+    u64::try_from(value).ok()
+}
+
+fn saturate(value: u128) -> u64 {
+    value.min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::counter::CostCurve;
+
+    fn sum_of_costs(curve: &CostCurve, workers: u64, quantity: u64) -> u64 {
+        (workers..workers + quantity)
+            .map(|owned| curve.cost(15, 115, owned))
+            .sum()
+    }
+
+    #[test]
+    fn test_linear() {
+        let curve = CostCurve::Linear;
+
+        assert_eq!(100, curve.cost(100, 150, 0));
+        assert_eq!(150, curve.cost(100, 150, 1));
+        assert_eq!(300, curve.cost(100, 150, 2));
+        assert_eq!(u64::MAX, curve.cost(u64::MAX, 200, 5));
+    }
+
+    #[test]
+    fn test_exponential() {
+        let curve = CostCurve::Exponential { percentage: 115 };
+
+        assert_eq!(15, curve.cost(15, 0, 0));
+        assert_eq!(17, curve.cost(15, 0, 1));
+        assert_eq!(61, curve.cost(15, 0, 10));
+        assert_eq!(u64::MAX, curve.cost(15, 0, 1_000_000));
+    }
+
+    #[test]
+    fn test_polynomial_and_stepped() {
+        let polynomial = CostCurve::Polynomial { exponent: 2 };
+        assert_eq!(10, polynomial.cost(10, 0, 0));
+        assert_eq!(90, polynomial.cost(10, 0, 2));
+
+        let stepped = CostCurve::Stepped {
+            every: 10,
+            percentage: 200,
+        };
+        assert_eq!(10, stepped.cost(10, 0, 9));
+        assert_eq!(20, stepped.cost(10, 0, 10));
+        assert_eq!(40, stepped.cost(10, 0, 25));
+    }
+
+    #[test]
+    fn test_custom() {
+        let curve = CostCurve::custom(|initial, workers| initial + workers * 7);
+
+        assert_eq!(24, curve.cost(10, 0, 2));
+        assert_eq!("Custom", format!("{:?}", curve));
+    }
+
+    #[test]
+    fn test_total_cost() {
+        let linear = CostCurve::Linear;
+        for (workers, quantity) in [(0, 1), (0, 250), (3, 7), (120, 333)] {
+            assert_eq!(
+                sum_of_costs(&linear, workers, quantity),
+                linear.total_cost(15, 115, workers, quantity)
+            );
+        }
+
+        // rounded once instead of per purchase
+        let exponential = CostCurve::Exponential { percentage: 115 };
+        assert_eq!(305, exponential.total_cost(15, 0, 0, 10));
+        assert_eq!(0, exponential.total_cost(15, 0, 0, 0));
+
+        let polynomial = CostCurve::Polynomial { exponent: 2 };
+        assert_eq!(10 + 40 + 90, polynomial.total_cost(10, 0, 0, 3));
+        assert_eq!(u64::MAX, polynomial.total_cost(10, 0, 0, u64::MAX));
+        assert_eq!(None, polynomial.checked_total_cost(10, 0, 0, 10_000_000));
+        assert_eq!(None, linear.checked_total_cost(u64::MAX, 100, 5, 2));
+    }
+
+    #[test]
+    fn test_constant() {
+        let constant = CostCurve::Exponential { percentage: 100 };
+        assert!(constant.is_closed_form());
+        assert_eq!(
+            15 * 1_000_000_000,
+            constant.total_cost(15, 0, 7, 1_000_000_000)
+        );
+        assert_eq!(u64::MAX / 15, constant.max_affordable(15, 0, 0, u64::MAX));
+        assert_eq!(u64::MAX, constant.max_affordable(0, 0, 0, 0));
+
+        assert!(!CostCurve::Polynomial { exponent: 2 }.is_closed_form());
+    }
+
+    #[test]
+    fn test_max_affordable() {
+        let exponential = CostCurve::Exponential { percentage: 115 };
+        assert_eq!(10, exponential.max_affordable(15, 0, 0, 305));
+        assert_eq!(9, exponential.max_affordable(15, 0, 0, 304));
+
+        let linear = CostCurve::Linear;
+        assert_eq!(u64::MAX, linear.max_affordable(0, 100, 0, 0));
+
+        let polynomial = CostCurve::Polynomial { exponent: 2 };
+        assert_eq!(2, polynomial.max_affordable(10, 0, 0, 139));
+        assert_eq!(3, polynomial.max_affordable(10, 0, 0, 140));
+    }
+
+    #[cfg(feature = "big_number")]
+    #[test]
+    fn test_big_cost() {
+        let exponential = CostCurve::Exponential { percentage: 1000 };
+        let cost = exponential.big_cost(15, 0, 1_000);
+
+        assert_eq!(1_001, cost.exponent());
+        assert!((cost.mantissa() - 1.5).abs() < 1e-9);
+        assert_eq!(u64::MAX, exponential.cost(15, 0, 1_000));
+
+        let linear = CostCurve::Linear;
+        assert_eq!(300, linear.big_cost(100, 150, 2).to_u64());
+    }
+
+    #[cfg(feature = "big_number")]
+    #[test]
+    fn test_big_cost_matches_cost() {
+        use crate::number::BigNumber;
+
+        let curves = [
+            CostCurve::Linear,
+            CostCurve::Exponential { percentage: 115 },
+            CostCurve::Polynomial { exponent: 2 },
+            CostCurve::Stepped {
+                every: 2,
+                percentage: 115,
+            },
+        ];
+
+        for curve in curves {
+            for workers in 0..20 {
+                assert_eq!(
+                    BigNumber::from(curve.cost(15, 115, workers)),
+                    curve.big_cost(15, 115, workers),
+                    "{:?} at {} workers",
+                    curve,
+                    workers
+                );
+            }
+        }
+
+        assert_eq!(
+            17,
+            CostCurve::Exponential { percentage: 115 }
+                .big_cost(15, 115, 1)
+                .to_u64()
+        );
+        assert_eq!(34, CostCurve::Linear.big_cost(10, 115, 3).to_u64());
+    }
+}
@@ -1,6 +1,11 @@
+use crate::counter::{BuyQuantity, CostCurve, Transaction, TransactionError, MAX_ITERATED};
+#[cfg(feature = "big_number")]
+use crate::number::{BigNumber, BigResource};
+use overworld_resource::resource::{GameResource, Resource};
 use std::ops::Deref;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::sync::{Mutex, MutexGuard};
 
 /// A thread-safe implementation of a counting object
 #[derive(Debug)]
@@ -14,12 +19,18 @@ pub struct CountingObject {
     /// Percentage effectiveness these workers are, starts at 100, lower to debuff, increase to buff
     /// This multiplies the increments_by value.
     effectiveness: AtomicU64,
+    /// The effectiveness before upgrades, ModifierStack builds on this instead of 100
+    base_effectiveness: AtomicU64,
     /// Numeric cost of the first purchase
     initial_cost: AtomicU64,
     /// Percentage of the initial_cost by which the cost of upgrading increases
     buy_cost: AtomicU64,
     /// Percentage of how much is refunded of the original buying price if you sell back a counter
     sell_cost: AtomicU64,
+    /// How the cost grows with every worker bought
+    cost_curve: CostCurve,
+    /// Held for the duration of buy_with and sell_to
+    transaction: Mutex<()>,
 }
 
 impl Clone for CountingObject {
@@ -29,9 +40,14 @@ impl Clone for CountingObject {
             counters: AtomicU64::new(self.counters.fetch_add(0, Ordering::SeqCst)),
             increments_by: AtomicU64::new(self.increments_by.fetch_add(0, Ordering::SeqCst)),
             effectiveness: AtomicU64::new(self.effectiveness.fetch_add(0, Ordering::SeqCst)),
+            base_effectiveness: AtomicU64::new(
+                self.base_effectiveness.fetch_add(0, Ordering::SeqCst),
+            ),
             initial_cost: AtomicU64::new(self.initial_cost.fetch_add(0, Ordering::SeqCst)),
             buy_cost: AtomicU64::new(self.buy_cost.fetch_add(0, Ordering::SeqCst)),
             sell_cost: AtomicU64::new(self.sell_cost.fetch_add(0, Ordering::SeqCst)),
+            cost_curve: self.cost_curve.clone(),
+            transaction: Mutex::new(()),
         }
     }
 }
@@ -46,14 +62,19 @@ impl CountingObject {
         buy_cost: AtomicU64,
         sell_cost: AtomicU64,
     ) -> Self {
+        let base_effectiveness = AtomicU64::new(effectiveness.load(Ordering::SeqCst));
+
         CountingObject {
             name,
             counters,
             increments_by,
             effectiveness,
+            base_effectiveness,
             initial_cost,
             buy_cost,
             sell_cost,
+            cost_curve: CostCurve::default(),
+            transaction: Mutex::new(()),
         }
     }
 
@@ -69,12 +90,29 @@ impl CountingObject {
             counters: AtomicU64::new(0),
             increments_by,
             effectiveness: AtomicU64::new(100),
+            base_effectiveness: AtomicU64::new(100),
             initial_cost,
             buy_cost,
             sell_cost,
+            cost_curve: CostCurve::default(),
+            transaction: Mutex::new(()),
         }
     }
 
+    /// Replaces the default linear cost curve
+    pub fn with_cost_curve(mut self, cost_curve: CostCurve) -> Self {
+        self.cost_curve = cost_curve;
+        self
+    }
+
+    pub fn set_cost_curve(&mut self, cost_curve: CostCurve) {
+        self.cost_curve = cost_curve;
+    }
+
+    pub fn cost_curve(&self) -> &CostCurve {
+        &self.cost_curve
+    }
+
     pub fn get_name(&self) -> &str {
         self.name.as_str()
     }
@@ -83,18 +121,31 @@ impl CountingObject {
         *self.counters.get_mut().deref()
     }
 
-    pub fn refund_cost(&mut self) -> u64 {
-        if self.workers() < 1 {
-            return 0;
-        }
+    /// Percentage effectiveness of the workers, 100 unless buffed or debuffed
+    pub fn effectiveness(&mut self) -> u64 {
+        *self.effectiveness.get_mut().deref()
+    }
 
-        let workers = self.workers() - 1u64;
+    /// Sets the effectiveness before upgrades, see ModifierStack for layered upgrades
+    pub fn set_effectiveness(&mut self, effectiveness: u64) {
+        self.base_effectiveness
+            .store(effectiveness, Ordering::SeqCst);
+        self.effectiveness.store(effectiveness, Ordering::SeqCst);
+    }
 
-        if workers == 1u64 {
-            return *self.initial_cost.get_mut().deref();
-        }
+    /// Percentage effectiveness of the workers before upgrades, 100 unless buffed or debuffed
+    pub fn base_effectiveness(&mut self) -> u64 {
+        *self.base_effectiveness.get_mut().deref()
+    }
+
+    /// Sets the effectiveness after upgrades, keeping the base effectiveness
+    pub fn set_modified_effectiveness(&mut self, effectiveness: u64) {
+        self.effectiveness.store(effectiveness, Ordering::SeqCst);
+    }
 
-        self.calc_buy_cost(workers) * self.sell_cost.get_mut().deref() / 100
+    pub fn refund_cost(&mut self) -> u64 {
+        let workers = self.workers();
+        self.refund_for(workers)
     }
 
     pub fn buy_cost(&mut self) -> u64 {
@@ -106,11 +157,12 @@ impl CountingObject {
         money >= self.buy_cost()
     }
 
-    /// non-threadsafe - one part of a multi-step transaction
+    /// non-threadsafe - one part of a multi-step transaction, see buy_with for an atomic purchase
     /// These ops should run in an isolated transaction to prevent race conditions
     /// - determine costs
     /// - add to counter
     /// - subtract from currency
+    ///
     /// returns Some(cost) if successful, None if not
     pub fn perform_buy(&mut self, money: u64) -> Option<u64> {
         if !self.can_afford_buy(money) {
@@ -122,11 +174,12 @@ impl CountingObject {
     }
 
     /// non-threadsafe - but less important to be threadsafe than buy, as the way this function is
-    /// set up there is very little risk of problems.
+    /// set up there is very little risk of problems. See sell_to for an atomic sale.
     /// These ops should run in an isolated transaction to prevent race conditions
     /// - determine costs
     /// - add to counter
     /// - subtract from currency
+    ///
     /// returns the amount of currency refunded, you need to manually add this to the money pool
     pub fn perform_sell(&mut self) -> u64 {
         if self.counters.get_mut().deref() < &1 {
@@ -137,25 +190,278 @@ impl CountingObject {
         refund_cost
     }
 
+    /// The combined cost of buying the next `quantity` workers
+    pub fn bulk_buy_cost(&mut self, quantity: u64) -> u64 {
+        let workers = self.workers();
+        self.bulk_buy_cost_from(workers, quantity)
+    }
+
+    /// The most workers that can be bought at once with `money`
+    pub fn max_affordable(&mut self, money: u64) -> u64 {
+        let workers = self.workers();
+        let initial_cost = *self.initial_cost.get_mut().deref();
+        let buy_cost = *self.buy_cost.get_mut().deref();
+
+        self.cost_curve
+            .max_affordable(initial_cost, buy_cost, workers, money)
+    }
+
+    /// The combined refund of selling `quantity` workers, at most every worker owned
+    pub fn bulk_refund(&mut self, quantity: u64) -> u64 {
+        let workers = self.workers();
+        self.refund_from(workers, quantity)
+    }
+
+    /// non-threadsafe - see perform_buy
+    ///
+    /// Buys all workers at once or none at all, Max buys as many as `money` affords.
+    /// returns Some((quantity, cost)) if successful, None if not
+    pub fn perform_bulk_buy(&mut self, quantity: BuyQuantity, money: u64) -> Option<(u64, u64)> {
+        let quantity = match quantity {
+            BuyQuantity::Exact(quantity) => quantity,
+            BuyQuantity::Max => self.max_affordable(money),
+        };
+
+        if quantity == 0 || quantity > u64::MAX - self.workers() {
+            return None;
+        }
+
+        let workers = self.workers();
+        let cost = self.checked_bulk_buy_cost_from(workers, quantity)?;
+        if cost > money {
+            return None;
+        }
+
+        self.counters.fetch_add(quantity, Ordering::SeqCst);
+        Some((quantity, cost))
+    }
+
+    /// non-threadsafe - see perform_sell
+    ///
+    /// Sells all workers at once or none at all, Max sells every worker.
+    /// returns Some((quantity, refund)) if successful, None if not
+    pub fn perform_bulk_sell(&mut self, quantity: BuyQuantity) -> Option<(u64, u64)> {
+        let workers = self.workers();
+        let quantity = match quantity {
+            BuyQuantity::Exact(quantity) => quantity,
+            BuyQuantity::Max => workers,
+        };
+
+        if quantity == 0 || quantity > workers {
+            return None;
+        }
+
+        let refund = self.bulk_refund(quantity);
+        self.counters.fetch_sub(quantity, Ordering::SeqCst);
+        Some((quantity, refund))
+    }
+
+    /// Buys workers with currency from `wallet` as a single atomic transaction
+    ///
+    /// Transactions on the same counter run one at a time, so the price cannot change between
+    /// paying and adding the workers. The wallet is debited in a single compare-and-swap,
+    /// so it can be shared between counters and threads and can never be double-spent.
+    pub fn buy_with(
+        &self,
+        wallet: &GameResource,
+        quantity: BuyQuantity,
+    ) -> Result<Transaction, TransactionError> {
+        let _transaction = self.lock_transaction();
+        let workers = self.counters.load(Ordering::SeqCst);
+
+        let quantity = match quantity {
+            BuyQuantity::Exact(quantity) => quantity,
+            BuyQuantity::Max => self.cost_curve.max_affordable(
+                self.initial_cost.load(Ordering::SeqCst),
+                self.buy_cost.load(Ordering::SeqCst),
+                workers,
+                wallet.value(),
+            ),
+        };
+
+        if quantity == 0 {
+            return Err(TransactionError::ZeroQuantity);
+        }
+
+        let total = workers
+            .checked_add(quantity)
+            .ok_or(TransactionError::Overflow)?;
+        let cost = self
+            .checked_bulk_buy_cost_from(workers, quantity)
+            .ok_or(TransactionError::Overflow)?;
+
+        wallet
+            .try_remove(cost)
+            .map_err(|available| TransactionError::InsufficientFunds { cost, available })?;
+        self.counters.store(total, Ordering::SeqCst);
+
+        Ok(Transaction {
+            quantity,
+            amount: cost,
+            workers: total,
+        })
+    }
+
+    /// Sells workers and adds the refund to `wallet` as a single atomic transaction
+    ///
+    /// Transactions on the same counter run one at a time, so the same worker can never be
+    /// sold twice. The wallet is credited in a single compare-and-swap.
+    pub fn sell_to(
+        &self,
+        wallet: &GameResource,
+        quantity: BuyQuantity,
+    ) -> Result<Transaction, TransactionError> {
+        let _transaction = self.lock_transaction();
+        let workers = self.counters.load(Ordering::SeqCst);
+
+        let quantity = match quantity {
+            BuyQuantity::Exact(quantity) => quantity,
+            BuyQuantity::Max => workers,
+        };
+
+        if quantity == 0 {
+            return Err(TransactionError::ZeroQuantity);
+        }
+
+        if quantity > workers {
+            return Err(TransactionError::InsufficientWorkers {
+                requested: quantity,
+                available: workers,
+            });
+        }
+
+        let refund = self.refund_from(workers, quantity);
+        let remaining = workers - quantity;
+
+        wallet
+            .try_add(refund)
+            .map_err(|_| TransactionError::Overflow)?;
+        self.counters.store(remaining, Ordering::SeqCst);
+
+        Ok(Transaction {
+            quantity,
+            amount: refund,
+            workers: remaining,
+        })
+    }
+
+    /// Production per tick, saturating at u64::MAX
     pub fn sum(&mut self) -> u64 {
-        ((self.counters.get_mut().deref() * self.increments_by.get_mut().deref())
-            * self.effectiveness.get_mut().deref())
-            / 100
+        let sum = (*self.counters.get_mut().deref() as u128)
+            .saturating_mul(*self.increments_by.get_mut().deref() as u128)
+            .saturating_mul(*self.effectiveness.get_mut().deref() as u128)
+            / 100;
+
+        sum.min(u64::MAX as u128) as u64
     }
 
-    fn calc_buy_cost(&mut self, workers: u64) -> u64 {
-        if workers == 0 {
-            return *self.initial_cost.get_mut().deref();
+    fn bulk_buy_cost_from(&self, workers: u64, quantity: u64) -> u64 {
+        self.checked_bulk_buy_cost_from(workers, quantity)
+            .unwrap_or(u64::MAX)
+    }
+
+    fn checked_bulk_buy_cost_from(&self, workers: u64, quantity: u64) -> Option<u64> {
+        self.cost_curve.checked_total_cost(
+            self.initial_cost.load(Ordering::SeqCst),
+            self.buy_cost.load(Ordering::SeqCst),
+            workers,
+            quantity,
+        )
+    }
+
+    /// The refund for selling a single worker when `owned` are owned
+    fn refund_for(&self, owned: u64) -> u64 {
+        if owned < 1 {
+            return 0;
         }
 
-        self.initial_cost.get_mut().deref() * (workers * self.buy_cost.get_mut().deref()) / 100
+        // Refunds part of what the last worker cost to buy
+        let cost = self.cost_curve.cost(
+            self.initial_cost.load(Ordering::SeqCst),
+            self.buy_cost.load(Ordering::SeqCst),
+            owned - 1,
+        );
+
+        (cost as u128 * self.sell_cost.load(Ordering::SeqCst) as u128 / 100).min(u64::MAX as u128)
+            as u64
+    }
+
+    /// The refund for selling `quantity` of `workers`, at most every worker
+    ///
+    /// Up to MAX_ITERATED workers every sale is rounded down separately, exactly like
+    /// selling them one at a time. Beyond that the refund is rounded down once over the total.
+    /// Either way the refund never exceeds what buying the same workers costs.
+    fn refund_from(&self, workers: u64, quantity: u64) -> u64 {
+        let quantity = quantity.min(workers);
+        if quantity == 0 {
+            return 0;
+        }
+
+        if quantity <= MAX_ITERATED {
+            return (workers - quantity + 1..=workers).fold(0u64, |total, owned| {
+                total.saturating_add(self.refund_for(owned))
+            });
+        }
+
+        // Selling with `owned` workers refunds part of the cost of worker `owned - 1`
+        let total = self.bulk_buy_cost_from(workers - quantity, quantity);
+        let sell_cost = self.sell_cost.load(Ordering::SeqCst);
+
+        (total as u128 * sell_cost as u128 / 100).min(u64::MAX as u128) as u64
+    }
+
+    fn lock_transaction(&self) -> MutexGuard<'_, ()> {
+        // The lock guards no data, so a panic while holding it leaves nothing inconsistent
+        self.transaction
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn calc_buy_cost(&mut self, workers: u64) -> u64 {
+        let initial_cost = *self.initial_cost.get_mut().deref();
+        let buy_cost = *self.buy_cost.get_mut().deref();
+
+        self.cost_curve.cost(initial_cost, buy_cost, workers)
+    }
+}
+
+#[cfg(feature = "big_number")]
+impl CountingObject {
+    /// Production per tick as a BigNumber, without saturating at u64::MAX
+    pub fn big_sum(&mut self) -> BigNumber {
+        BigNumber::from(*self.counters.get_mut().deref())
+            * BigNumber::from(*self.increments_by.get_mut().deref())
+            * BigNumber::new(*self.effectiveness.get_mut().deref() as f64 / 100., 0)
+    }
+
+    /// The cost of the next worker as a BigNumber, see CostCurve::big_cost
+    pub fn big_buy_cost(&mut self) -> BigNumber {
+        let workers = self.workers();
+        let initial_cost = *self.initial_cost.get_mut().deref();
+        let buy_cost = *self.buy_cost.get_mut().deref();
+
+        self.cost_curve.big_cost(initial_cost, buy_cost, workers)
+    }
+
+    /// Buys a worker, paid for with a BigResource
+    ///
+    /// returns Some(cost) if successful, None if not
+    pub fn perform_big_buy(&mut self, resource: &mut BigResource) -> Option<BigNumber> {
+        let cost = self.big_buy_cost();
+        resource.remove(cost).ok()?;
+
+        self.counters.fetch_add(1, Ordering::SeqCst);
+        Some(cost)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::counter::CountingObject;
+    use crate::counter::{BuyQuantity, CostCurve, CountingObject, TransactionError};
+    use overworld_resource::resource::{GameResource, Resource};
     use std::sync::atomic::AtomicU64;
+    use std::sync::Arc;
+    use std::thread;
 
     fn new_counting_object() -> CountingObject {
         CountingObject::new(
@@ -220,4 +526,204 @@ mod tests {
 
         assert_eq!(800, co.refund_cost());
     }
+
+    #[test]
+    fn test_counting_object_cost_curve() {
+        let mut co =
+            new_counting_object().with_cost_curve(CostCurve::Exponential { percentage: 200 });
+
+        for expected_cost in [100, 200, 400, 800] {
+            assert_eq!(Some(expected_cost), co.perform_buy(1000));
+        }
+
+        assert!(!co.can_afford_buy(1000));
+        assert_eq!(1600, co.buy_cost());
+    }
+
+    #[test]
+    fn test_counting_object_bulk_buy() {
+        let mut co = new_counting_object();
+        let mut single = new_counting_object();
+
+        let expected: u64 = (0..10).map(|_| single.perform_buy(u64::MAX).unwrap()).sum();
+        assert_eq!(expected, co.bulk_buy_cost(10));
+
+        assert_eq!(
+            None,
+            co.perform_bulk_buy(BuyQuantity::Exact(10), expected - 1)
+        );
+        assert_eq!(
+            Some((10, expected)),
+            co.perform_bulk_buy(BuyQuantity::Exact(10), expected)
+        );
+
+        // the 11th worker costs 1000, the 12th 1100
+        assert_eq!(2, co.max_affordable(2100));
+        assert_eq!(Some((1, 1000)), co.perform_bulk_buy(BuyQuantity::Max, 2099));
+        assert_eq!(None, co.perform_bulk_buy(BuyQuantity::Max, 0));
+    }
+
+    #[test]
+    fn test_counting_object_bulk_buy_large_quantities() {
+        let mut polynomial =
+            new_counting_object().with_cost_curve(CostCurve::Polynomial { exponent: 2 });
+        assert_eq!(
+            None,
+            polynomial.perform_bulk_buy(BuyQuantity::Exact(u64::MAX - 1), u64::MAX)
+        );
+        assert_eq!(0, polynomial.workers());
+
+        let mut constant =
+            new_counting_object().with_cost_curve(CostCurve::Exponential { percentage: 100 });
+        assert_eq!(
+            Some((u64::MAX / 100, u64::MAX / 100 * 100)),
+            constant.perform_bulk_buy(BuyQuantity::Max, u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_counting_object_bulk_sell() {
+        let mut co = new_counting_object();
+        co.perform_bulk_buy(BuyQuantity::Exact(10), u64::MAX)
+            .unwrap();
+
+        let mut single = co.clone();
+        let expected: u64 = (0..10).map(|_| single.perform_sell()).sum();
+        assert_eq!(expected, co.bulk_refund(10));
+        assert_eq!(900 + 800, co.bulk_refund(2));
+
+        assert_eq!(None, co.perform_bulk_sell(BuyQuantity::Exact(11)));
+        assert_eq!(Some((10, expected)), co.perform_bulk_sell(BuyQuantity::Max));
+        assert_eq!(0, co.workers());
+    }
+
+    #[test]
+    fn test_counting_object_bulk_sell_rounding() {
+        let mut co = CountingObject::new(
+            String::from("test"),
+            AtomicU64::new(1),
+            AtomicU64::new(7),
+            AtomicU64::new(115),
+            AtomicU64::new(33),
+        )
+        .with_cost_curve(CostCurve::Exponential { percentage: 115 });
+        co.perform_bulk_buy(BuyQuantity::Exact(25), u64::MAX)
+            .unwrap();
+
+        for quantity in [1, 2, 7, 25] {
+            let mut single = co.clone();
+            let expected: u64 = (0..quantity).map(|_| single.perform_sell()).sum();
+
+            assert_eq!(expected, co.bulk_refund(quantity));
+        }
+    }
+
+    #[test]
+    fn test_counting_object_round_trip() {
+        let curves = [
+            (7, 107, CostCurve::Exponential { percentage: 107 }),
+            (100, 50, CostCurve::Linear),
+        ];
+
+        for (initial_cost, buy_cost, curve) in curves {
+            let co = CountingObject::new(
+                String::from("test"),
+                AtomicU64::new(1),
+                AtomicU64::new(initial_cost),
+                AtomicU64::new(buy_cost),
+                AtomicU64::new(100),
+            )
+            .with_cost_curve(curve);
+            let wallet = GameResource::new(String::from("gold"), 10_000);
+
+            let mut single = co.clone();
+            let expected: u64 = (0..11).map(|_| single.perform_buy(u64::MAX).unwrap()).sum();
+
+            for _ in 0..5 {
+                let bought = co.buy_with(&wallet, BuyQuantity::Exact(11)).unwrap();
+                assert_eq!(expected, bought.amount);
+
+                co.sell_to(&wallet, BuyQuantity::Max).unwrap();
+                assert!(wallet.value() <= 10_000);
+            }
+        }
+    }
+
+    #[test]
+    fn test_counting_object_transactions() {
+        let co = new_counting_object();
+        let wallet = GameResource::new(String::from("gold"), 250);
+
+        let transaction = co.buy_with(&wallet, BuyQuantity::Exact(2)).unwrap();
+        assert_eq!(200, transaction.amount);
+        assert_eq!(50, wallet.value());
+
+        assert_eq!(
+            Err(TransactionError::InsufficientFunds {
+                cost: 200,
+                available: 50
+            }),
+            co.buy_with(&wallet, BuyQuantity::Exact(1))
+        );
+        assert_eq!(
+            Err(TransactionError::InsufficientWorkers {
+                requested: 3,
+                available: 2
+            }),
+            co.sell_to(&wallet, BuyQuantity::Exact(3))
+        );
+
+        let transaction = co.sell_to(&wallet, BuyQuantity::Max).unwrap();
+        assert_eq!(0, transaction.workers);
+        assert_eq!(50 + 100 + 100, wallet.value());
+    }
+
+    #[test]
+    fn test_counting_object_concurrent_transactions() {
+        let co = Arc::new(new_counting_object().with_cost_curve(CostCurve::custom(|_, _| 10)));
+        let wallet = Arc::new(GameResource::new(String::from("gold"), 1000));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let co = Arc::clone(&co);
+                let wallet = Arc::clone(&wallet);
+
+                thread::spawn(move || {
+                    (0..50)
+                        .filter(|_| co.buy_with(&wallet, BuyQuantity::Exact(1)).is_ok())
+                        .count() as u64
+                })
+            })
+            .collect();
+
+        let bought: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(100, bought);
+        assert_eq!(0, wallet.value());
+        assert_eq!(100, co.counters.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "big_number")]
+    #[test]
+    fn test_counting_object_big_buy() {
+        use crate::number::{BigNumber, BigResource};
+
+        let mut co =
+            new_counting_object().with_cost_curve(CostCurve::Exponential { percentage: 1000 });
+        let mut wallet = BigResource::new(String::from("gold"), BigNumber::new(1., 100));
+
+        assert_eq!(
+            BigNumber::from(100),
+            co.perform_big_buy(&mut wallet).unwrap()
+        );
+        assert_eq!(
+            BigNumber::from(1000),
+            co.perform_big_buy(&mut wallet).unwrap()
+        );
+        assert_eq!(BigNumber::from(1000), co.big_sum());
+        assert!(co.big_buy_cost() < wallet.value());
+
+        let mut empty = BigResource::new(String::from("gold"), BigNumber::ZERO);
+        assert_eq!(None, co.perform_big_buy(&mut empty));
+        assert_eq!(2, co.workers());
+    }
 }
@@ -1,3 +1,9 @@
+mod buy_quantity;
+mod cost_curve;
 mod counting_object;
+mod transaction;
 
+pub use buy_quantity::BuyQuantity;
+pub use cost_curve::{CostCurve, MAX_ITERATED};
 pub use counting_object::CountingObject;
+pub use transaction::{Transaction, TransactionError};
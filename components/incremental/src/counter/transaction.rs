@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter};
+
+/// A completed purchase or sale of workers
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transaction {
+    /// Number of workers bought or sold
+    pub quantity: u64,
+    /// Currency taken from the wallet when buying, or added to it when selling
+    pub amount: u64,
+    /// Number of workers owned after the transaction
+    pub workers: u64,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TransactionError {
+    /// Nothing would be bought or sold
+    ZeroQuantity,
+    /// The wallet cannot pay for the purchase
+    InsufficientFunds { cost: u64, available: u64 },
+    /// Fewer workers are owned than were requested to be sold
+    InsufficientWorkers { requested: u64, available: u64 },
+    /// The worker count, the wallet or the cost would overflow
+    Overflow,
+}
+
+impl Display for TransactionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionError::ZeroQuantity => write!(f, "Cannot buy or sell zero workers"),
+            TransactionError::InsufficientFunds { cost, available } => {
+                write!(f, "Costs {} but only {} is available", cost, available)
+            }
+            TransactionError::InsufficientWorkers {
+                requested,
+                available,
+            } => write!(
+                f,
+                "Cannot sell {} workers, only {} are owned",
+                requested, available
+            ),
+            TransactionError::Overflow => write!(f, "Transaction would overflow"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
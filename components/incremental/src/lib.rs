@@ -1,2 +1,6 @@
+pub mod automation;
 pub mod counter;
+pub mod number;
 pub mod progress;
+pub mod simulation;
+pub mod upgrade;
@@ -0,0 +1,351 @@
+use crate::number::NumberFormat;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Differences in exponent past which the smaller number no longer affects the larger
+const PRECISION: i64 = 17;
+
+/// A number stored as mantissa * 10^exponent, for values far beyond u64 and f64
+///
+/// The mantissa is normalized to 1 <= |mantissa| < 10, or 0 for zero.
+/// Precision is that of an f64, roughly 15 significant digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "BigNumberData"))]
+pub struct BigNumber {
+    mantissa: f64,
+    exponent: i64,
+}
+
+/// Deserialized numbers go through `new`, as comparisons rely on a normalized mantissa
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BigNumberData {
+    mantissa: f64,
+    exponent: i64,
+}
+
+#[cfg(feature = "serde")]
+impl From<BigNumberData> for BigNumber {
+    fn from(data: BigNumberData) -> Self {
+        Self::new(data.mantissa, data.exponent)
+    }
+}
+
+impl BigNumber {
+    pub const ZERO: Self = Self {
+        mantissa: 0.,
+        exponent: 0,
+    };
+
+    pub const ONE: Self = Self {
+        mantissa: 1.,
+        exponent: 0,
+    };
+
+    /// Creates a normalized number, non-finite mantissas are treated as zero
+    pub fn new(mantissa: f64, exponent: i64) -> Self {
+        if !mantissa.is_finite() || mantissa == 0. {
+            return Self::ZERO;
+        }
+
+        let shift = mantissa.abs().log10().floor() as i64;
+        let mut mantissa = mantissa / 10f64.powi(shift as i32);
+        let mut exponent = exponent.saturating_add(shift);
+
+        // log10 can be off by one due to rounding
+        if mantissa.abs() >= 10. {
+            mantissa /= 10.;
+            exponent = exponent.saturating_add(1);
+        } else if mantissa.abs() < 1. {
+            mantissa *= 10.;
+            exponent = exponent.saturating_sub(1);
+        }
+
+        Self { mantissa, exponent }
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        Self::new(value, 0)
+    }
+
+    pub fn mantissa(&self) -> f64 {
+        self.mantissa
+    }
+
+    pub fn exponent(&self) -> i64 {
+        self.exponent
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0.
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0.
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            mantissa: self.mantissa.abs(),
+            exponent: self.exponent,
+        }
+    }
+
+    /// The base 10 logarithm of the absolute value, negative infinity for zero
+    pub fn log10(&self) -> f64 {
+        if self.is_zero() {
+            return f64::NEG_INFINITY;
+        }
+
+        self.exponent as f64 + self.mantissa.abs().log10()
+    }
+
+    /// Raises the number to a power, negative numbers keep their sign for odd integer powers
+    pub fn pow(&self, power: f64) -> Self {
+        if power == 0. {
+            return Self::ONE;
+        }
+
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+
+        // Small integer powers of the mantissa stay finite and are more precise than logarithms
+        if power.fract() == 0. && power.abs() <= 256. {
+            return Self::new(
+                self.mantissa.powi(power as i32),
+                self.exponent.saturating_mul(power as i64),
+            );
+        }
+
+        let log = self.log10() * power;
+        if !log.is_finite() {
+            return Self::ZERO;
+        }
+
+        let exponent = log.floor();
+        let mantissa = 10f64.powf(log - exponent);
+        let negative = self.is_negative() && power.fract() == 0. && power % 2. != 0.;
+
+        Self::new(if negative { -mantissa } else { mantissa }, exponent as i64)
+    }
+
+    /// The value as f64, infinite if it does not fit
+    pub fn to_f64(&self) -> f64 {
+        if self.exponent > f64::MAX_10_EXP as i64 {
+            return self.mantissa.signum() * f64::INFINITY;
+        }
+
+        if self.exponent < f64::MIN_10_EXP as i64 - PRECISION {
+            return 0.;
+        }
+
+        self.mantissa * 10f64.powi(self.exponent as i32)
+    }
+
+    /// The value as u64, rounded down and saturating at 0 and u64::MAX
+    pub fn to_u64(&self) -> u64 {
+        // float to int casts saturate
+        self.to_f64().floor() as u64
+    }
+}
+
+impl Default for BigNumber {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<u64> for BigNumber {
+    fn from(value: u64) -> Self {
+        Self::new(value as f64, 0)
+    }
+}
+
+impl Add for BigNumber {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (big, small) = if self.exponent >= other.exponent || other.is_zero() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        if big.is_zero() {
+            return small;
+        }
+
+        let difference = big.exponent.saturating_sub(small.exponent);
+        if small.is_zero() || difference > PRECISION {
+            return big;
+        }
+
+        Self::new(
+            big.mantissa + small.mantissa / 10f64.powi(difference as i32),
+            big.exponent,
+        )
+    }
+}
+
+impl Neg for BigNumber {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            mantissa: -self.mantissa,
+            exponent: self.exponent,
+        }
+    }
+}
+
+impl Sub for BigNumber {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for BigNumber {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.mantissa * other.mantissa,
+            self.exponent.saturating_add(other.exponent),
+        )
+    }
+}
+
+impl Div for BigNumber {
+    type Output = Self;
+
+    /// Panics when dividing by zero, like integer division
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "attempt to divide by zero");
+
+        Self::new(
+            self.mantissa / other.mantissa,
+            self.exponent.saturating_sub(other.exponent),
+        )
+    }
+}
+
+impl AddAssign for BigNumber {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for BigNumber {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for BigNumber {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl PartialOrd for BigNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let sign = |number: &Self| number.mantissa.partial_cmp(&0.);
+
+        match (sign(self)?, sign(other)?) {
+            (a, b) if a != b => Some(a.cmp(&b)),
+            (Ordering::Equal, _) => Some(Ordering::Equal),
+            (sign, _) => {
+                let ordering = match self.exponent.cmp(&other.exponent) {
+                    Ordering::Equal => self.mantissa.abs().partial_cmp(&other.mantissa.abs())?,
+                    ordering => ordering,
+                };
+
+                // A larger exponent means a smaller value for negative numbers
+                Some(match sign {
+                    Ordering::Less => ordering.reverse(),
+                    _ => ordering,
+                })
+            }
+        }
+    }
+}
+
+impl Display for BigNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", NumberFormat::default().format_big(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::BigNumber;
+
+    #[test]
+    fn test_normalize() {
+        let value = BigNumber::new(1234., 30);
+
+        assert_eq!(1.234, value.mantissa());
+        assert_eq!(33, value.exponent());
+        assert_eq!(BigNumber::new(-5., 2), BigNumber::new(-500., 0));
+        assert_eq!(BigNumber::ZERO, BigNumber::new(f64::NAN, 5));
+        assert_eq!(BigNumber::from(1000), BigNumber::new(1., 3));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = BigNumber::new(5., 300);
+        let b = BigNumber::new(5., 299);
+
+        assert_eq!(BigNumber::new(5.5, 300), a + b);
+        assert_eq!(BigNumber::new(4.5, 300), a - b);
+        assert_eq!(BigNumber::new(-4.5, 300), b - a);
+        assert_eq!(BigNumber::ZERO, a - a);
+        assert_eq!(a, a + BigNumber::ONE);
+
+        assert_eq!(BigNumber::new(2.5, 600), a * b);
+        assert_eq!(BigNumber::from(10), a / b);
+        assert_eq!(BigNumber::new(1., 1000), BigNumber::from(10).pow(1000.));
+        assert_eq!(BigNumber::new(-8., 0), BigNumber::new(-2., 0).pow(3.));
+        assert_eq!(1500, (BigNumber::from(1000) + 500.into()).to_u64());
+
+        let huge = BigNumber::new(1., i64::MAX);
+        let tiny = BigNumber::new(1., i64::MIN);
+        assert_eq!(huge, huge + tiny);
+        assert_eq!(huge, tiny + huge);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(BigNumber::new(1., 400) > BigNumber::new(9., 399));
+        assert!(BigNumber::new(-1., 400) < BigNumber::new(-9., 399));
+        assert!(BigNumber::new(-1., 400) < BigNumber::ZERO);
+        assert!(BigNumber::ZERO < BigNumber::new(1., -400));
+        assert!(BigNumber::ZERO <= BigNumber::ZERO);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", BigNumber::ZERO.to_string());
+        assert_eq!("1.50K", BigNumber::from(1500).to_string());
+        assert_eq!("123.40dt", BigNumber::new(1.234, 308).to_string());
+        assert_eq!("-500.00ab", BigNumber::new(-5., 20).to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_normalizes() {
+        let value: BigNumber =
+            serde_json::from_str(r#"{"mantissa":1234.0,"exponent":30}"#).unwrap();
+
+        assert_eq!(BigNumber::new(1.234, 33), value);
+        assert!(value > BigNumber::new(9., 32));
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::number::BigNumber;
+use overworld_resource::resource::Resource;
+use std::fmt::{Display, Formatter};
+
+/// A resource whose value is a BigNumber, for amounts that outgrow GameResource
+///
+/// The inherent methods work with BigNumber. The Resource trait is implemented as well,
+/// with u64 amounts and a value that saturates at u64::MAX.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigResource {
+    pub resource_name: String,
+    pub resource_value: BigNumber,
+}
+
+impl BigResource {
+    pub fn new(name: String, value: BigNumber) -> Self {
+        Self {
+            resource_name: name,
+            resource_value: value,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.resource_name.clone()
+    }
+
+    pub fn value(&self) -> BigNumber {
+        self.resource_value
+    }
+
+    /// Adds to the value, returns the previous value
+    pub fn add(&mut self, amount: BigNumber) -> BigNumber {
+        let previous = self.resource_value;
+        self.resource_value += amount;
+
+        previous
+    }
+
+    /// Removes from the value, returns the previous value
+    pub fn remove(&mut self, amount: BigNumber) -> Result<BigNumber, &str> {
+        if self.resource_value < amount {
+            return Err("Cannot remove more than the current value of the resource.");
+        }
+
+        let previous = self.resource_value;
+        self.resource_value -= amount;
+
+        Ok(previous)
+    }
+}
+
+impl Resource for BigResource {
+    fn new(name: String, value: u64) -> Self {
+        BigResource::new(name, BigNumber::from(value))
+    }
+
+    fn name(&self) -> String {
+        BigResource::name(self)
+    }
+
+    /// The value rounded down, saturating at u64::MAX
+    fn value(&self) -> u64 {
+        self.resource_value.to_u64()
+    }
+
+    fn add(&mut self, amount: u64) -> u64 {
+        BigResource::add(self, BigNumber::from(amount)).to_u64()
+    }
+
+    fn remove(&mut self, amount: u64) -> Result<u64, &str> {
+        BigResource::remove(self, BigNumber::from(amount)).map(|previous| previous.to_u64())
+    }
+}
+
+impl Display for BigResource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.resource_name, self.resource_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::{BigNumber, BigResource};
+    use overworld_resource::resource::Resource;
+
+    #[test]
+    fn test_big_resource() {
+        let mut resource = BigResource::new(String::from("gold"), BigNumber::new(1., 30));
+
+        assert_eq!(BigNumber::new(1., 30), resource.add(BigNumber::new(5., 29)));
+        assert_eq!(BigNumber::new(1.5, 30), resource.value());
+
+        assert!(resource.remove(BigNumber::new(2., 30)).is_err());
+        assert!(resource.remove(BigNumber::new(1.5, 30)).is_ok());
+        assert!(resource.value().is_zero());
+        assert_eq!("gold (0)", resource.to_string());
+    }
+
+    #[test]
+    fn test_resource_trait() {
+        fn earn<R: Resource>(resource: &mut R, amount: u64) -> u64 {
+            resource.add(amount);
+            resource.value()
+        }
+
+        let mut resource = <BigResource as Resource>::new(String::from("gold"), 100);
+        assert_eq!(150, earn(&mut resource, 50));
+        assert!(Resource::remove(&mut resource, 200).is_err());
+        assert_eq!(Ok(150), Resource::remove(&mut resource, 150));
+
+        resource.add(BigNumber::new(1., 30));
+        assert_eq!(u64::MAX, Resource::value(&resource));
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(feature = "big_number")]
+mod big_number;
+#[cfg(feature = "big_number")]
+mod big_resource;
+mod number_format;
+
+#[cfg(feature = "big_number")]
+pub use big_number::BigNumber;
+#[cfg(feature = "big_number")]
+pub use big_resource::BigResource;
+pub use number_format::{Locale, Notation, NumberFormat};
@@ -0,0 +1,380 @@
+#[cfg(feature = "big_number")]
+use crate::number::BigNumber;
+
+/// Short suffixes for every power of 1000, after these come two letter suffixes (aa, ab, ..., zz)
+const SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Notation {
+    /// 1234567890 as "1.23B", falls back to scientific past "zz"
+    #[default]
+    Suffix,
+    /// 1234567890 as "1.23e9"
+    Scientific,
+    /// 1234567890 as "1.23e9", with exponents that are a multiple of 3, e.g. "12.35e6"
+    Engineering,
+    /// 1234567890 as "1,234,567,890", falls back to scientific for numbers beyond f64
+    Grouped,
+}
+
+/// Separators used to write numbers
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Locale {
+    /// Separates groups of three digits, None to not group digits
+    pub grouping: Option<char>,
+    pub decimal: char,
+}
+
+impl Locale {
+    /// 1,234,567.89
+    pub const ENGLISH: Locale = Locale {
+        grouping: Some(','),
+        decimal: '.',
+    };
+    /// 1.234.567,89
+    pub const GERMAN: Locale = Locale {
+        grouping: Some('.'),
+        decimal: ',',
+    };
+    /// 1 234 567,89, grouped with a narrow no-break space
+    pub const FRENCH: Locale = Locale {
+        grouping: Some('\u{202F}'),
+        decimal: ',',
+    };
+    /// 1'234'567.89
+    pub const SWISS: Locale = Locale {
+        grouping: Some('\''),
+        decimal: '.',
+    };
+    /// 1234567.89
+    pub const UNGROUPED: Locale = Locale {
+        grouping: None,
+        decimal: '.',
+    };
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::ENGLISH
+    }
+}
+
+/// Formats numbers for display, e.g. 1234567890 as "1.23B"
+///
+/// Numbers below the threshold are written in full, rounded to `precision` decimals
+/// without trailing zeros. Larger numbers are always written with `precision` decimals.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberFormat {
+    notation: Notation,
+    precision: usize,
+    locale: Locale,
+    threshold: u64,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            notation: Notation::default(),
+            precision: 2,
+            locale: Locale::default(),
+            threshold: 1000,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn new(notation: Notation) -> Self {
+        Self {
+            notation,
+            ..Self::default()
+        }
+    }
+
+    /// Number of decimals to round to, defaults to 2
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Numbers below this are written in full, defaults to 1000
+    pub fn with_threshold(mut self, threshold: u64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    pub fn format_u64(&self, value: u64) -> String {
+        if value < self.threshold || self.notation == Notation::Grouped {
+            return self.group(&value.to_string());
+        }
+
+        self.format_f64(value as f64)
+    }
+
+    pub fn format_f64(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        if value.abs() < self.threshold as f64 || self.notation == Notation::Grouped {
+            return self.plain(value);
+        }
+
+        // Zero has no exponent to normalize to
+        if value == 0. {
+            return self.plain(value);
+        }
+
+        let (mantissa, exponent) = normalize(value.abs());
+        self.scaled(value < 0., mantissa, exponent)
+    }
+
+    #[cfg(feature = "big_number")]
+    pub fn format_big(&self, value: &BigNumber) -> String {
+        if value.exponent() < f64::MAX_10_EXP as i64 {
+            return self.format_f64(value.to_f64());
+        }
+
+        self.scaled(
+            value.is_negative(),
+            value.mantissa().abs(),
+            value.exponent(),
+        )
+    }
+
+    /// Writes `mantissa * 10^exponent` in the configured notation, the mantissa is in [1, 10)
+    fn scaled(&self, negative: bool, mantissa: f64, exponent: i64) -> String {
+        let notation = match self.notation {
+            Notation::Grouped => Notation::Scientific,
+            notation => notation,
+        };
+
+        // There are no suffixes below 1, so these numbers are written in full
+        if notation == Notation::Suffix && exponent < 0 {
+            let value = mantissa * 10f64.powi(exponent as i32);
+            return self.plain(if negative { -value } else { value });
+        }
+
+        let step = match notation {
+            Notation::Scientific => 1,
+            _ => 3,
+        };
+        let shift = exponent.rem_euclid(step);
+        let mut digits = self.round(mantissa * 10f64.powi(shift as i32));
+        let mut exponent = exponent - shift;
+
+        // Rounding can carry over into the next step, e.g. 999.999K to 1000.00K
+        if digits >= 10f64.powi(step as i32) {
+            digits = self.round(digits / 10f64.powi(step as i32));
+            exponent += step;
+        }
+
+        let sign = if negative { "-" } else { "" };
+        let digits = self.localize(&format!("{:.*}", self.precision, digits));
+
+        if notation == Notation::Suffix {
+            if let Some(suffix) = suffix(exponent / 3) {
+                return format!("{}{}{}", sign, digits, suffix);
+            }
+        }
+
+        format!("{}{}e{}", sign, digits, exponent)
+    }
+
+    /// Writes the number in full, without trailing zeros
+    fn plain(&self, value: f64) -> String {
+        let rounded = self.round(value);
+        let mut text = format!("{:.*}", self.precision, rounded.abs());
+
+        if text.contains('.') {
+            text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+        }
+
+        let sign = if rounded < 0. && text != "0" { "-" } else { "" };
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text.as_str(), None),
+        };
+
+        match fraction {
+            Some(fraction) => format!(
+                "{}{}{}{}",
+                sign,
+                self.group(integer),
+                self.locale.decimal,
+                fraction
+            ),
+            None => format!("{}{}", sign, self.group(integer)),
+        }
+    }
+
+    /// Inserts the grouping separator into a string of digits
+    fn group(&self, digits: &str) -> String {
+        let Some(separator) = self.locale.grouping else {
+            return digits.to_string();
+        };
+
+        // A separator goes before every third digit counting from the end
+        let offset = digits.len() % 3;
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && i % 3 == offset {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+
+        grouped
+    }
+
+    fn localize(&self, number: &str) -> String {
+        number.replace('.', &self.locale.decimal.to_string())
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        // Beyond 17 decimals an f64 has no more precision to round
+        let factor = 10f64.powi(self.precision.min(17) as i32);
+        (value * factor).round() / factor
+    }
+}
+
+/// Splits a positive number into a mantissa in [1, 10) and an exponent
+fn normalize(value: f64) -> (f64, i64) {
+    let exponent = value.log10().floor() as i64;
+    let mantissa = value / 10f64.powi(exponent as i32);
+
+    // log10 can be off by one due to rounding
+    if mantissa >= 10. {
+        (mantissa / 10., exponent + 1)
+    } else if mantissa < 1. {
+        (mantissa * 10., exponent - 1)
+    } else {
+        (mantissa, exponent)
+    }
+}
+
+/// The suffix for `1000^group`, None past "zz"
+fn suffix(group: i64) -> Option<String> {
+    if let Some(suffix) = SUFFIXES.get(group as usize) {
+        return Some(suffix.to_string());
+    }
+
+    let index = group - SUFFIXES.len() as i64;
+    if index >= 26 * 26 {
+        return None;
+    }
+
+    let letter = |offset: i64| char::from(b'a' + offset as u8);
+    Some(format!("{}{}", letter(index / 26), letter(index % 26)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::{Locale, Notation, NumberFormat};
+
+    #[test]
+    fn test_suffix() {
+        let format = NumberFormat::default();
+
+        assert_eq!("999", format.format_u64(999));
+        assert_eq!("1.00K", format.format_u64(1000));
+        assert_eq!("1.23B", format.format_u64(1_234_567_890));
+        assert_eq!("1.00M", format.format_u64(999_999));
+        assert_eq!("18.45ab", format.format_u64(u64::MAX));
+        assert_eq!("-1.50ab", format.format_f64(-1.5e18));
+        assert_eq!("1.00dr", format.format_f64(1e300));
+    }
+
+    #[test]
+    fn test_scientific_and_engineering() {
+        let scientific = NumberFormat::new(Notation::Scientific).with_precision(3);
+        assert_eq!("1.235e9", scientific.format_u64(1_234_567_890));
+        assert_eq!("1.000e4", scientific.format_f64(9999.9999));
+
+        let engineering = NumberFormat::new(Notation::Engineering);
+        assert_eq!("12.35e6", engineering.format_u64(12_345_678));
+        assert_eq!("1.23e9", engineering.format_u64(1_234_567_890));
+        assert_eq!("12.5", engineering.format_f64(12.5));
+    }
+
+    #[test]
+    fn test_grouped() {
+        let format = NumberFormat::new(Notation::Grouped);
+        assert_eq!("1,234,567,890", format.format_u64(1_234_567_890));
+        assert_eq!("-1,234.57", format.format_f64(-1234.5678));
+        assert_eq!("100", format.format_u64(100));
+        assert_eq!("0", format.format_f64(-0.001));
+
+        let german = format.with_locale(Locale::GERMAN);
+        assert_eq!("1.234.567,5", german.format_f64(1_234_567.5));
+        assert_eq!(
+            "1234567",
+            format.with_locale(Locale::UNGROUPED).format_u64(1_234_567)
+        );
+    }
+
+    #[test]
+    fn test_threshold_and_locale() {
+        let format = NumberFormat::default()
+            .with_threshold(1_000_000)
+            .with_locale(Locale::GERMAN);
+
+        assert_eq!("999.999", format.format_u64(999_999));
+        assert_eq!("1,23M", format.format_u64(1_234_567));
+        assert_eq!("inf", format.format_f64(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_below_one() {
+        let format = NumberFormat::default().with_threshold(0);
+
+        assert_eq!("0", format.format_u64(0));
+        assert_eq!("0", format.format_f64(-0.));
+        assert_eq!("0.5", format.format_f64(0.5));
+        assert_eq!("-0.05", format.format_f64(-0.05));
+        assert_eq!("0.12", format.format_f64(0.123));
+        assert_eq!("NaN", format.format_f64(f64::NAN));
+
+        let scientific = NumberFormat::new(Notation::Scientific).with_threshold(0);
+        assert_eq!("0", scientific.format_f64(0.));
+        assert_eq!("5.00e-1", scientific.format_f64(0.5));
+    }
+
+    #[cfg(feature = "big_number")]
+    #[test]
+    fn test_big_number() {
+        use crate::number::BigNumber;
+
+        let format = NumberFormat::default();
+        assert_eq!("1.50K", format.format_big(&BigNumber::from(1500)));
+        assert_eq!("123.40dt", format.format_big(&BigNumber::new(1.234, 308)));
+        assert_eq!("1.00e3000", format.format_big(&BigNumber::new(1., 3000)));
+        assert_eq!(
+            "1.00e100000",
+            NumberFormat::new(Notation::Grouped).format_big(&BigNumber::new(1., 100_000))
+        );
+    }
+}
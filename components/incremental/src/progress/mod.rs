@@ -1,28 +1,62 @@
+use crate::number::NumberFormat;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct ProgressBar {
     progress: f64,
     max: u64,
+    /// Progress gained per second by `elapse`, None to only fill manually
+    rate: Option<f64>,
+    /// Whether the bar restarts when `elapse` completes it
+    looping: bool,
 }
 
 impl ProgressBar {
     pub fn new(max: u64) -> Self {
-        Self { progress: 0., max }
+        Self {
+            progress: 0.,
+            max,
+            rate: None,
+            looping: false,
+        }
     }
 
     pub fn new_in_progress(progress: f64, max: u64) -> Self {
-        Self { progress, max }
+        Self {
+            progress,
+            ..Self::new(max)
+        }
     }
 
     pub fn new_finished(max: u64) -> Self {
         Self {
             progress: max as f64,
-            max,
+            ..Self::new(max)
         }
     }
 
+    /// A bar that fills completely over `duration` as time elapses
+    pub fn new_timed(max: u64, duration: Duration) -> Self {
+        let mut bar = Self::new(max);
+        bar.set_duration(duration);
+        bar
+    }
+
+    /// A bar that fills by `per_second` as time elapses
+    pub fn new_with_rate(max: u64, per_second: f64) -> Self {
+        let mut bar = Self::new(max);
+        bar.set_rate(Some(per_second));
+        bar
+    }
+
+    /// Restart the bar whenever `elapse` completes it, e.g. for production cycles
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
     pub fn percentage(&self) -> f64 {
         (self.progress / self.max as f64) * 100_f64
     }
@@ -85,19 +119,163 @@ impl ProgressBar {
     pub fn as_range(&self) -> std::ops::Range<f64> {
         0.0..self.max as f64
     }
+
+    /// Progress gained per second, negative or non-finite rates are treated as None
+    pub fn set_rate(&mut self, per_second: Option<f64>) {
+        self.rate = per_second.filter(|rate| rate.is_finite() && *rate >= 0.);
+    }
+
+    pub fn get_rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Sets the rate so that an empty bar fills over `duration`, a zero duration fills instantly
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.rate = if duration.is_zero() {
+            Some(f64::INFINITY)
+        } else {
+            Some(self.max as f64 / duration.as_secs_f64())
+        };
+    }
+
+    /// Time it takes to fill the bar from empty, None without a rate
+    pub fn get_duration(&self) -> Option<Duration> {
+        self.time_for(self.max as f64)
+    }
+
+    /// Time until the bar is finished, None without a rate
+    pub fn time_remaining(&self) -> Option<Duration> {
+        self.time_for(self.max as f64 - self.progress)
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Fills the bar at its rate, returns how often it was completed
+    ///
+    /// A bar that does not loop completes at most once and then stays finished.
+    /// A looping bar restarts on completion and keeps the overflow, so a delta
+    /// that covers several cycles completes it several times.
+    pub fn elapse(&mut self, delta: Duration) -> u64 {
+        let Some(rate) = self.rate else {
+            return 0;
+        };
+
+        let max = self.max as f64;
+        if max == 0. || (!self.looping && self.is_finished()) {
+            return 0;
+        }
+
+        let total = if rate.is_infinite() {
+            max
+        } else {
+            self.progress + rate * delta.as_secs_f64()
+        };
+
+        if total < max {
+            self.progress = total;
+            return 0;
+        }
+
+        if !self.looping {
+            self.progress = max;
+            return 1;
+        }
+
+        // An instant bar completes once per call rather than infinitely often
+        if rate.is_infinite() {
+            self.progress = 0.;
+            return 1;
+        }
+
+        let completions = (total / max).floor();
+        self.progress = (total - completions * max).clamp(0., max);
+
+        // float to int casts saturate
+        completions as u64
+    }
+
+    fn time_for(&self, progress: f64) -> Option<Duration> {
+        let rate = self.rate?;
+        if progress <= 0. || rate.is_infinite() {
+            return Some(Duration::ZERO);
+        }
+
+        if rate == 0. {
+            return Some(Duration::MAX);
+        }
+
+        Some(Duration::try_from_secs_f64(progress / rate).unwrap_or(Duration::MAX))
+    }
 }
 
 impl Display for ProgressBar {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let max_width: usize = (self.max as f64).log10() as usize + 3;
+        let format = NumberFormat::default();
+        let max = format.format_u64(self.max);
+        let max_width: usize = max.chars().count() + 2;
 
         write!(
             f,
             "{: >max_width$}/{} ({: >3}%)",
-            (self.progress * 100.0).round() / 100.0,
-            self.max,
+            format.format_f64(self.progress),
+            max,
             self.percentage().round(),
             max_width = max_width
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::progress::ProgressBar;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timed() {
+        let mut bar = ProgressBar::new_timed(100, Duration::from_secs(4));
+
+        assert_eq!(Some(Duration::from_secs(4)), bar.get_duration());
+        assert_eq!(0, bar.elapse(Duration::from_secs(1)));
+        assert_eq!(25., bar.get_progress());
+        assert_eq!(Some(Duration::from_secs(3)), bar.time_remaining());
+
+        assert_eq!(1, bar.elapse(Duration::from_secs(60)));
+        assert!(bar.is_finished());
+        assert_eq!(0, bar.elapse(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_looping() {
+        let mut bar = ProgressBar::new_with_rate(10, 4.).with_looping(true);
+
+        assert_eq!(0, bar.elapse(Duration::from_secs(2)));
+        // 8 + 4 * 8 = 40
+        assert_eq!(4, bar.elapse(Duration::from_secs(8)));
+        assert_eq!(0., bar.get_progress());
+
+        assert_eq!(2, bar.elapse(Duration::from_millis(6250)));
+        assert_eq!(5., bar.get_progress());
+    }
+
+    #[test]
+    fn test_manual_and_instant() {
+        let mut manual = ProgressBar::new(10);
+        assert_eq!(0, manual.elapse(Duration::from_secs(10)));
+        assert_eq!(None, manual.time_remaining());
+
+        let mut instant = ProgressBar::new_timed(10, Duration::ZERO).with_looping(true);
+        assert_eq!(1, instant.elapse(Duration::from_secs(1)));
+        assert_eq!(1, instant.elapse(Duration::from_secs(100)));
+
+        let mut stalled = ProgressBar::new_with_rate(10, 0.);
+        assert_eq!(Some(Duration::MAX), stalled.time_remaining());
+        stalled.set_rate(Some(-1.));
+        assert_eq!(None, stalled.get_rate());
+    }
+}
@@ -0,0 +1,5 @@
+mod offline_progress;
+mod simulation_engine;
+
+pub use offline_progress::{OfflinePurchase, OfflineReport, OfflineSettings};
+pub use simulation_engine::{Production, Simulation, SimulationError};
@@ -0,0 +1,271 @@
+use crate::number::NumberFormat;
+use crate::simulation::{Production, Simulation};
+use overworld_resource::resource::Resource;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A counter that is bought automatically while offline, paid for with a resource
+///
+/// Unlike an `AutobuyRule` there is no reserve, spending limit or priority, a counter is
+/// bought as soon as the whole balance covers it. Rules the player set up for an `Autobuyer`
+/// are not applied while offline, so both can spend a balance differently.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OfflinePurchase {
+    pub counter: String,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OfflineSettings {
+    /// Percentage of the regular production earned while offline
+    pub efficiency: u64,
+    /// The longest time that is credited, None for no limit
+    pub max_duration: Option<Duration>,
+    /// Counters bought as soon as they are affordable, the quickest to afford goes first
+    pub purchases: Vec<OfflinePurchase>,
+    /// The most purchases made in a single offline period
+    pub max_purchases: u64,
+}
+
+impl Default for OfflineSettings {
+    fn default() -> Self {
+        Self {
+            efficiency: 100,
+            max_duration: None,
+            purchases: vec![],
+            max_purchases: 1000,
+        }
+    }
+}
+
+/// A summary of what happened while the player was away
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OfflineReport {
+    /// Time the player was away
+    pub away: Duration,
+    /// Time that was credited, after the cap
+    pub credited: Duration,
+    pub efficiency: u64,
+    /// Amount produced per resource name
+    pub produced: BTreeMap<String, u64>,
+    /// Workers bought per counter name
+    pub purchased: BTreeMap<String, u64>,
+    /// Amount spent on purchases per resource name
+    pub spent: BTreeMap<String, u64>,
+}
+
+impl OfflineReport {
+    pub fn purchases(&self) -> u64 {
+        self.purchased.values().sum()
+    }
+}
+
+impl Display for OfflineReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let minutes = self.away.as_secs() / 60;
+        write!(
+            f,
+            "While you were away for {}h {}m",
+            minutes / 60,
+            minutes % 60
+        )?;
+
+        if self.credited < self.away {
+            let minutes = self.credited.as_secs() / 60;
+            write!(f, " ({}h {}m credited)", minutes / 60, minutes % 60)?;
+        }
+        writeln!(f, ":")?;
+
+        let format = NumberFormat::default();
+
+        for (resource, amount) in &self.produced {
+            writeln!(f, "  {} +{}", resource, format.format_u64(*amount))?;
+        }
+
+        for (counter, amount) in &self.purchased {
+            writeln!(f, "  {} bought {}", counter, format.format_u64(*amount))?;
+        }
+
+        for (resource, amount) in &self.spent {
+            writeln!(f, "  {} -{}", resource, format.format_u64(*amount))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation {
+    /// Credits production for time spent offline without simulating every tick
+    ///
+    /// Production is constant between purchases, so time is skipped from one
+    /// automated purchase to the next, each jump is calculated in closed form.
+    pub fn offline_progress(
+        &mut self,
+        away: Duration,
+        settings: &OfflineSettings,
+    ) -> OfflineReport {
+        let credited = settings.max_duration.map_or(away, |max| away.min(max));
+        let mut remaining = credited;
+        let mut production = Production::default();
+        let mut report = OfflineReport {
+            away,
+            credited,
+            efficiency: settings.efficiency,
+            ..OfflineReport::default()
+        };
+
+        while report.purchases() < settings.max_purchases {
+            let next = self.next_purchase(settings);
+
+            let Some((purchase, wait)) = next.filter(|(_, wait)| *wait <= remaining) else {
+                break;
+            };
+
+            self.simulate(wait, settings.efficiency, &mut production);
+            remaining -= wait;
+
+            if let Some(cost) = self.buy(purchase) {
+                *report
+                    .purchased
+                    .entry(purchase.counter.clone())
+                    .or_insert(0) += 1;
+                let spent = report.spent.entry(purchase.currency.clone()).or_insert(0);
+                *spent = spent.saturating_add(cost);
+            }
+        }
+
+        self.simulate(remaining, settings.efficiency, &mut production);
+        report.produced = production.produced;
+
+        report
+    }
+
+    /// The purchase that becomes affordable first, and how long that takes
+    fn next_purchase<'a>(
+        &mut self,
+        settings: &'a OfflineSettings,
+    ) -> Option<(&'a OfflinePurchase, Duration)> {
+        let rates = self.production_per_tick();
+        let tick = self.tick_length().as_nanos();
+
+        settings
+            .purchases
+            .iter()
+            .filter_map(|purchase| {
+                let cost = self.counter_mut(&purchase.counter)?.buy_cost();
+                let balance = self.resource(&purchase.currency)?.value();
+                if balance >= cost {
+                    return Some((purchase, Duration::ZERO));
+                }
+
+                let rate = *rates.get(&purchase.currency)? as u128 * settings.efficiency as u128;
+                if rate == 0 {
+                    return None;
+                }
+
+                let nanos = ((cost - balance) as u128 * tick * 100).div_ceil(rate);
+                Some((
+                    purchase,
+                    Duration::from_nanos(nanos.min(u64::MAX as u128) as u64),
+                ))
+            })
+            .min_by_key(|(_, wait)| *wait)
+    }
+
+    fn buy(&mut self, purchase: &OfflinePurchase) -> Option<u64> {
+        let balance = self.resource(&purchase.currency)?.value();
+        let cost = self.counter_mut(&purchase.counter)?.perform_buy(balance)?;
+
+        self.resource_mut(&purchase.currency)?.remove(cost).ok()?;
+        Some(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::counter::CountingObject;
+    use crate::simulation::{OfflinePurchase, OfflineSettings, Simulation};
+    use overworld_resource::resource::{GameResource, Resource};
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    const HOUR: u64 = 60 * 60;
+
+    fn new_simulation() -> Simulation {
+        let mut simulation = Simulation::new(Duration::from_secs(1)).unwrap();
+        simulation
+            .add_resource(GameResource::new(String::from("gold"), 0))
+            .unwrap();
+        simulation
+            .add_counter(
+                CountingObject::from(
+                    String::from("miner"),
+                    AtomicU64::new(1),
+                    AtomicU64::new(1),
+                    AtomicU64::new(100),
+                    AtomicU64::new(1000),
+                    AtomicU64::new(100),
+                    AtomicU64::new(100),
+                ),
+                "gold",
+            )
+            .unwrap();
+
+        simulation
+    }
+
+    #[test]
+    fn test_offline_production() {
+        let mut simulation = new_simulation();
+
+        let report =
+            simulation.offline_progress(Duration::from_secs(8 * HOUR), &OfflineSettings::default());
+
+        assert_eq!(Some(&28_800), report.produced.get("gold"));
+        assert_eq!(28_800, simulation.resource("gold").unwrap().value());
+    }
+
+    #[test]
+    fn test_cap_and_efficiency() {
+        let mut simulation = new_simulation();
+        let settings = OfflineSettings {
+            efficiency: 50,
+            max_duration: Some(Duration::from_secs(2 * HOUR)),
+            ..OfflineSettings::default()
+        };
+
+        let report = simulation.offline_progress(Duration::from_secs(8 * HOUR), &settings);
+
+        assert_eq!(Duration::from_secs(2 * HOUR), report.credited);
+        assert_eq!(3600, simulation.resource("gold").unwrap().value());
+        assert_eq!(
+            "While you were away for 8h 0m (2h 0m credited):\n  gold +3.60K\n",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn test_automated_purchases() {
+        let mut simulation = new_simulation();
+        let settings = OfflineSettings {
+            purchases: vec![OfflinePurchase {
+                counter: String::from("miner"),
+                currency: String::from("gold"),
+            }],
+            ..OfflineSettings::default()
+        };
+
+        // 1000s to afford the second miner (1000 gold), then 1000s for the third (2000 gold)
+        let report = simulation.offline_progress(Duration::from_secs(2500), &settings);
+
+        assert_eq!(Some(&2), report.purchased.get("miner"));
+        assert_eq!(Some(&3000), report.spent.get("gold"));
+        assert_eq!(3, simulation.counter_mut("miner").unwrap().workers());
+        assert_eq!(500 * 3, simulation.resource("gold").unwrap().value());
+        assert_eq!(Some(&4500), report.produced.get("gold"));
+    }
+}
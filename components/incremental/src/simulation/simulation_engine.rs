@@ -0,0 +1,387 @@
+use crate::counter::CountingObject;
+use overworld_resource::resource::{GameResource, Resource};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SimulationError {
+    /// No resource with this name exists in the simulation
+    UnknownResource(String),
+    /// A resource with this name already exists in the simulation
+    DuplicateResource(String),
+    /// A counter with this name already exists in the simulation
+    DuplicateCounter(String),
+    /// The tick length is zero, so counters would produce infinitely
+    ZeroTickLength,
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulationError::UnknownResource(name) => write!(f, "Unknown resource '{}'", name),
+            SimulationError::DuplicateResource(name) => {
+                write!(f, "Resource '{}' already exists", name)
+            }
+            SimulationError::DuplicateCounter(name) => {
+                write!(f, "Counter '{}' already exists", name)
+            }
+            SimulationError::ZeroTickLength => write!(f, "Tick length must not be zero"),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// What a call to `Simulation::advance` produced
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Production {
+    /// Time simulated, with a fixed step this excludes time left in the accumulator
+    pub elapsed: Duration,
+    /// Number of steps simulated
+    pub steps: u64,
+    /// Amount produced per resource name
+    pub produced: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone)]
+struct Producer {
+    counter: CountingObject,
+    resource: String,
+    /// Production that did not add up to a whole unit yet, in units * nanoseconds * percent
+    remainder: u128,
+}
+
+/// Advances time for a set of CountingObjects, crediting what they produce to GameResources
+///
+/// A counter produces `CountingObject::sum` every `tick_length`. Time that does not add up to
+/// a whole unit of production is carried over to the next call, so nothing is lost to rounding.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    tick_length: Duration,
+    /// Fixed step to advance in, None to advance by whatever time is given
+    step: Option<Duration>,
+    /// Time given that has not been simulated yet, only used with a fixed step
+    accumulator: Duration,
+    elapsed: Duration,
+    resources: BTreeMap<String, GameResource>,
+    producers: Vec<Producer>,
+}
+
+impl Simulation {
+    /// Creates a simulation advancing at a variable step, `tick_length` must not be zero
+    pub fn new(tick_length: Duration) -> Result<Self, SimulationError> {
+        if tick_length.is_zero() {
+            return Err(SimulationError::ZeroTickLength);
+        }
+
+        Ok(Self {
+            tick_length,
+            step: None,
+            accumulator: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            resources: BTreeMap::new(),
+            producers: vec![],
+        })
+    }
+
+    pub fn tick_length(&self) -> Duration {
+        self.tick_length
+    }
+
+    /// Advance in steps of this size, leftover time is kept until it adds up to a step.
+    /// A zero step is treated as a variable step.
+    pub fn set_fixed_step(&mut self, step: Option<Duration>) {
+        self.step = step.filter(|step| !step.is_zero());
+    }
+
+    pub fn fixed_step(&self) -> Option<Duration> {
+        self.step
+    }
+
+    /// Time given to `advance` that has not been simulated yet
+    pub fn pending(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// Total time simulated
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn add_resource(&mut self, resource: GameResource) -> Result<(), SimulationError> {
+        let name = resource.name();
+        if self.resources.contains_key(&name) {
+            return Err(SimulationError::DuplicateResource(name));
+        }
+
+        self.resources.insert(name, resource);
+        Ok(())
+    }
+
+    pub fn resource(&self, name: &str) -> Option<&GameResource> {
+        self.resources.get(name)
+    }
+
+    pub fn resource_mut(&mut self, name: &str) -> Option<&mut GameResource> {
+        self.resources.get_mut(name)
+    }
+
+    pub fn resources(&self) -> impl Iterator<Item = &GameResource> {
+        self.resources.values()
+    }
+
+    /// Adds a counter whose production is credited to the named resource
+    pub fn add_counter(
+        &mut self,
+        counter: CountingObject,
+        resource: &str,
+    ) -> Result<(), SimulationError> {
+        if !self.resources.contains_key(resource) {
+            return Err(SimulationError::UnknownResource(resource.to_string()));
+        }
+
+        if self.counter(counter.get_name()).is_some() {
+            return Err(SimulationError::DuplicateCounter(
+                counter.get_name().to_string(),
+            ));
+        }
+
+        self.producers.push(Producer {
+            counter,
+            resource: resource.to_string(),
+            remainder: 0,
+        });
+        Ok(())
+    }
+
+    pub fn counter(&self, name: &str) -> Option<&CountingObject> {
+        self.producers
+            .iter()
+            .find(|producer| producer.counter.get_name() == name)
+            .map(|producer| &producer.counter)
+    }
+
+    pub fn counter_mut(&mut self, name: &str) -> Option<&mut CountingObject> {
+        self.producers
+            .iter_mut()
+            .find(|producer| producer.counter.get_name() == name)
+            .map(|producer| &mut producer.counter)
+    }
+
+    pub fn counters(&self) -> impl Iterator<Item = &CountingObject> {
+        self.producers.iter().map(|producer| &producer.counter)
+    }
+
+    /// Mutable access to every counter, e.g. for `ModifierStack::apply`
+    pub fn counters_mut(&mut self) -> impl Iterator<Item = &mut CountingObject> {
+        self.producers
+            .iter_mut()
+            .map(|producer| &mut producer.counter)
+    }
+
+    /// Advances the simulation, e.g. by the time since the last frame
+    pub fn advance(&mut self, delta: Duration) -> Production {
+        let mut production = Production::default();
+
+        match self.step {
+            None => self.simulate(delta, 100, &mut production),
+            Some(step) => {
+                self.accumulator = self.accumulator.saturating_add(delta);
+
+                while self.accumulator >= step {
+                    self.accumulator -= step;
+                    self.simulate(step, 100, &mut production);
+                }
+            }
+        }
+
+        production
+    }
+
+    /// Production per tick for every resource that is produced
+    pub fn production_per_tick(&mut self) -> BTreeMap<String, u64> {
+        let mut rates = BTreeMap::new();
+
+        for producer in self.producers.iter_mut() {
+            let rate: &mut u64 = rates.entry(producer.resource.clone()).or_insert(0);
+            *rate = rate.saturating_add(producer.counter.sum());
+        }
+
+        rates
+    }
+
+    /// Produces `delta` worth of output at `efficiency` percent
+    pub(super) fn simulate(
+        &mut self,
+        delta: Duration,
+        efficiency: u64,
+        production: &mut Production,
+    ) {
+        let tick = self.tick_length.as_nanos() * 100;
+
+        for producer in self.producers.iter_mut() {
+            let total = (producer.counter.sum() as u128)
+                .saturating_mul(delta.as_nanos())
+                .saturating_mul(efficiency as u128)
+                .saturating_add(producer.remainder);
+            let produced = (total / tick).min(u64::MAX as u128) as u64;
+            producer.remainder = total % tick;
+
+            if produced == 0 {
+                continue;
+            }
+
+            // Resources are full at u64::MAX, only what fits is credited and reported
+            let Some(resource) = self.resources.get_mut(&producer.resource) else {
+                continue;
+            };
+            let added = produced.min(u64::MAX - resource.value());
+            if added == 0 {
+                continue;
+            }
+            resource.add(added);
+
+            let entry = production
+                .produced
+                .entry(producer.resource.clone())
+                .or_insert(0);
+            *entry = entry.saturating_add(added);
+        }
+
+        production.elapsed = production.elapsed.saturating_add(delta);
+        production.steps += 1;
+        self.elapsed = self.elapsed.saturating_add(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::counter::CountingObject;
+    use crate::simulation::{Simulation, SimulationError};
+    use overworld_resource::resource::{GameResource, Resource};
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    fn new_counting_object(name: &str, workers: u64, increments_by: u64) -> CountingObject {
+        CountingObject::from(
+            String::from(name),
+            AtomicU64::new(workers),
+            AtomicU64::new(increments_by),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+        )
+    }
+
+    fn new_simulation() -> Simulation {
+        let mut simulation = Simulation::new(Duration::from_secs(1)).unwrap();
+        simulation
+            .add_resource(GameResource::new(String::from("gold"), 0))
+            .unwrap();
+        simulation
+            .add_resource(GameResource::new(String::from("wood"), 0))
+            .unwrap();
+        simulation
+            .add_counter(new_counting_object("miner", 2, 5), "gold")
+            .unwrap();
+        simulation
+            .add_counter(new_counting_object("lumberjack", 1, 1), "wood")
+            .unwrap();
+
+        simulation
+    }
+
+    #[test]
+    fn test_variable_step() {
+        let mut simulation = new_simulation();
+
+        let production = simulation.advance(Duration::from_secs(3));
+        assert_eq!(1, production.steps);
+        assert_eq!(Some(&30), production.produced.get("gold"));
+        assert_eq!(30, simulation.resource("gold").unwrap().value());
+        assert_eq!(3, simulation.resource("wood").unwrap().value());
+    }
+
+    #[test]
+    fn test_fractional_accumulation() {
+        let mut simulation = new_simulation();
+
+        for _ in 0..60 {
+            simulation.advance(Duration::from_millis(16));
+        }
+
+        // 960ms of a lumberjack producing one wood per second
+        assert_eq!(0, simulation.resource("wood").unwrap().value());
+        assert_eq!(9, simulation.resource("gold").unwrap().value());
+
+        simulation.advance(Duration::from_millis(40));
+        assert_eq!(1, simulation.resource("wood").unwrap().value());
+        assert_eq!(10, simulation.resource("gold").unwrap().value());
+    }
+
+    #[test]
+    fn test_full_resource() {
+        let mut simulation = new_simulation();
+        simulation.resource_mut("gold").unwrap().add(u64::MAX - 10);
+
+        let production = simulation.advance(Duration::from_secs(3));
+        assert_eq!(Some(&10), production.produced.get("gold"));
+        assert_eq!(u64::MAX, simulation.resource("gold").unwrap().value());
+
+        let production = simulation.advance(Duration::from_secs(3));
+        assert_eq!(None, production.produced.get("gold"));
+        assert_eq!(Some(&3), production.produced.get("wood"));
+
+        simulation.set_fixed_step(Some(Duration::MAX));
+        simulation.advance(Duration::from_secs(1));
+        assert_eq!(1, simulation.advance(Duration::MAX).steps);
+        assert_eq!(Duration::MAX, simulation.elapsed());
+    }
+
+    #[test]
+    fn test_fixed_step() {
+        let mut simulation = new_simulation();
+        simulation.set_fixed_step(Some(Duration::from_millis(500)));
+
+        let production = simulation.advance(Duration::from_millis(1200));
+        assert_eq!(2, production.steps);
+        assert_eq!(Duration::from_millis(200), simulation.pending());
+        assert_eq!(10, simulation.resource("gold").unwrap().value());
+
+        let production = simulation.advance(Duration::from_millis(300));
+        assert_eq!(1, production.steps);
+        assert_eq!(Duration::ZERO, simulation.pending());
+        assert_eq!(Duration::from_millis(1500), simulation.elapsed());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut simulation = new_simulation();
+
+        assert_eq!(
+            Err(SimulationError::UnknownResource(String::from("stone"))),
+            simulation.add_counter(new_counting_object("mason", 1, 1), "stone")
+        );
+        assert_eq!(
+            Err(SimulationError::DuplicateCounter(String::from("miner"))),
+            simulation.add_counter(new_counting_object("miner", 1, 1), "gold")
+        );
+        assert_eq!(
+            Err(SimulationError::DuplicateResource(String::from("gold"))),
+            simulation.add_resource(GameResource::new(String::from("gold"), 0))
+        );
+        assert_eq!(
+            Some(SimulationError::ZeroTickLength),
+            Simulation::new(Duration::ZERO).err()
+        );
+
+        simulation
+            .counter_mut("miner")
+            .unwrap()
+            .perform_buy(u64::MAX);
+        assert_eq!(3, simulation.counter_mut("miner").unwrap().workers());
+    }
+}
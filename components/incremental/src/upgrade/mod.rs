@@ -0,0 +1,5 @@
+mod modifier;
+mod modifier_stack;
+
+pub use modifier::{Modifier, ModifierKind, ModifierTarget, Synergy};
+pub use modifier_stack::{Contribution, EffectivenessBreakdown, ModifierStack};
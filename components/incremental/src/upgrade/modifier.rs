@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierKind {
+    /// Percentage points added to the base effectiveness, summed with other additive bonuses
+    Additive(u64),
+    /// Percentage the effectiveness is multiplied by, 200 doubles it
+    Multiplicative(u64),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierTarget {
+    /// Applies to every counter
+    Global,
+    /// Applies to the counter with this name
+    Counter(String),
+}
+
+impl ModifierTarget {
+    pub fn applies_to(&self, counter: &str) -> bool {
+        match self {
+            ModifierTarget::Global => true,
+            ModifierTarget::Counter(name) => name == counter,
+        }
+    }
+}
+
+/// An upgrade or buff that changes the effectiveness of counters
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifier {
+    /// Where the modifier comes from, e.g. "reinforced_pickaxe" or "frenzy"
+    pub source: String,
+    pub target: ModifierTarget,
+    pub kind: ModifierKind,
+    /// Time left before the modifier expires, None if permanent
+    pub remaining: Option<Duration>,
+}
+
+/// Every worker of one counter boosts another, e.g. each Farm boosts Grandma by 1%
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Synergy {
+    /// The counter whose workers grant the bonus
+    pub source: String,
+    /// The counter that receives the bonus
+    pub target: String,
+    /// Percentage points added per worker of the source
+    pub percentage_per_worker: u64,
+}
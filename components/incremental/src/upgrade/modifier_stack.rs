@@ -0,0 +1,297 @@
+use crate::counter::CountingObject;
+use crate::upgrade::{Modifier, ModifierKind, ModifierTarget, Synergy};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The percentage a single source contributes to a counter's effectiveness
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Contribution {
+    pub source: String,
+    pub percentage: u64,
+}
+
+/// How a counter's effectiveness was calculated
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectivenessBreakdown {
+    /// Effectiveness before any modifiers, 100 unless the counter has its own
+    pub base: u64,
+    /// Percentage points added by additive modifiers
+    pub additive: Vec<Contribution>,
+    /// Percentage points added by the workers of other counters
+    pub synergies: Vec<Contribution>,
+    /// Percentages the sum is multiplied by
+    pub multipliers: Vec<Contribution>,
+    /// The resulting effectiveness
+    pub effectiveness: u64,
+}
+
+/// Layers of upgrades, buffs and synergies that determine the effectiveness of counters
+///
+/// Additive modifiers and synergies are summed with the base of 100%,
+/// the sum is then multiplied by every multiplicative modifier in turn.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierStack {
+    modifiers: Vec<Modifier>,
+    synergies: Vec<Synergy>,
+}
+
+impl ModifierStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn synergies(&self) -> &[Synergy] {
+        &self.synergies
+    }
+
+    /// Adds a permanent modifier, e.g. a purchased upgrade
+    pub fn add(&mut self, source: &str, target: ModifierTarget, kind: ModifierKind) {
+        self.modifiers.push(Modifier {
+            source: source.to_string(),
+            target,
+            kind,
+            remaining: None,
+        });
+    }
+
+    /// Adds a modifier that expires after `duration` has elapsed
+    pub fn add_buff(
+        &mut self,
+        source: &str,
+        target: ModifierTarget,
+        kind: ModifierKind,
+        duration: Duration,
+    ) {
+        self.modifiers.push(Modifier {
+            source: source.to_string(),
+            target,
+            kind,
+            remaining: Some(duration),
+        });
+    }
+
+    pub fn add_synergy(&mut self, source: &str, target: &str, percentage_per_worker: u64) {
+        self.synergies.push(Synergy {
+            source: source.to_string(),
+            target: target.to_string(),
+            percentage_per_worker,
+        });
+    }
+
+    /// Removes every modifier from the given source, returns true if any were removed
+    pub fn remove(&mut self, source: &str) -> bool {
+        let before = self.modifiers.len();
+        self.modifiers.retain(|modifier| modifier.source != source);
+
+        before != self.modifiers.len()
+    }
+
+    /// Advances time, expiring buffs
+    pub fn elapse(&mut self, duration: Duration) {
+        self.modifiers
+            .retain_mut(|modifier| match modifier.remaining {
+                None => true,
+                Some(remaining) => {
+                    modifier.remaining = Some(remaining.saturating_sub(duration));
+                    remaining > duration
+                }
+            });
+    }
+
+    /// Calculates the effectiveness of a counter, given the workers owned per counter name
+    pub fn breakdown(
+        &self,
+        counter: &str,
+        workers: &BTreeMap<String, u64>,
+    ) -> EffectivenessBreakdown {
+        self.breakdown_from(counter, 100, workers)
+    }
+
+    /// Like breakdown, but starting from the given base effectiveness instead of 100
+    pub fn breakdown_from(
+        &self,
+        counter: &str,
+        base: u64,
+        workers: &BTreeMap<String, u64>,
+    ) -> EffectivenessBreakdown {
+        let mut breakdown = EffectivenessBreakdown {
+            base,
+            ..EffectivenessBreakdown::default()
+        };
+
+        for modifier in self.modifiers.iter() {
+            if !modifier.target.applies_to(counter) {
+                continue;
+            }
+
+            let (contributions, percentage) = match modifier.kind {
+                ModifierKind::Additive(percentage) => (&mut breakdown.additive, percentage),
+                ModifierKind::Multiplicative(percentage) => {
+                    (&mut breakdown.multipliers, percentage)
+                }
+            };
+
+            contributions.push(Contribution {
+                source: modifier.source.clone(),
+                percentage,
+            });
+        }
+
+        for synergy in self.synergies.iter().filter(|s| s.target == counter) {
+            let owned = workers.get(&synergy.source).copied().unwrap_or(0);
+
+            breakdown.synergies.push(Contribution {
+                source: synergy.source.clone(),
+                percentage: owned.saturating_mul(synergy.percentage_per_worker),
+            });
+        }
+
+        let sum = breakdown
+            .additive
+            .iter()
+            .chain(breakdown.synergies.iter())
+            .fold(breakdown.base as u128, |sum, c| {
+                sum.saturating_add(c.percentage as u128)
+            });
+
+        let effectiveness = breakdown.multipliers.iter().fold(sum, |total, c| {
+            total.saturating_mul(c.percentage as u128) / 100
+        });
+
+        breakdown.effectiveness = effectiveness.min(u64::MAX as u128) as u64;
+        breakdown
+    }
+
+    /// Sets the effectiveness of every counter, building on its base effectiveness
+    pub fn apply<'a>(&self, counters: impl IntoIterator<Item = &'a mut CountingObject>) {
+        let mut counters: Vec<&mut CountingObject> = counters.into_iter().collect();
+        let workers: BTreeMap<String, u64> = counters
+            .iter_mut()
+            .map(|counter| (counter.get_name().to_string(), counter.workers()))
+            .collect();
+
+        for counter in counters {
+            let base = counter.base_effectiveness();
+            let breakdown = self.breakdown_from(counter.get_name(), base, &workers);
+            counter.set_modified_effectiveness(breakdown.effectiveness);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::counter::CountingObject;
+    use crate::upgrade::{ModifierKind, ModifierStack, ModifierTarget};
+    use std::collections::BTreeMap;
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    fn new_counting_object(name: &str, workers: u64) -> CountingObject {
+        CountingObject::from(
+            String::from(name),
+            AtomicU64::new(workers),
+            AtomicU64::new(10),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+        )
+    }
+
+    fn counter(name: &str) -> ModifierTarget {
+        ModifierTarget::Counter(String::from(name))
+    }
+
+    #[test]
+    fn test_stacking() {
+        let mut stack = ModifierStack::new();
+        stack.add("gloves", counter("grandma"), ModifierKind::Additive(50));
+        stack.add(
+            "rolling_pin",
+            counter("grandma"),
+            ModifierKind::Multiplicative(200),
+        );
+        stack.add(
+            "heavenly",
+            ModifierTarget::Global,
+            ModifierKind::Multiplicative(150),
+        );
+        stack.add("fertilizer", counter("farm"), ModifierKind::Additive(25));
+
+        let workers = BTreeMap::new();
+        let breakdown = stack.breakdown("grandma", &workers);
+
+        assert_eq!(1, breakdown.additive.len());
+        assert_eq!(2, breakdown.multipliers.len());
+        assert_eq!(150 * 2 * 3 / 2, breakdown.effectiveness);
+        assert_eq!(150, stack.breakdown("mine", &workers).effectiveness);
+    }
+
+    #[test]
+    fn test_synergy() {
+        let mut stack = ModifierStack::new();
+        stack.add_synergy("farm", "grandma", 1);
+
+        let mut counters = [
+            new_counting_object("grandma", 1),
+            new_counting_object("farm", 30),
+        ];
+        stack.apply(counters.iter_mut());
+
+        assert_eq!(130, counters[0].effectiveness());
+        assert_eq!(100, counters[1].effectiveness());
+        assert_eq!(13, counters[0].sum());
+    }
+
+    #[test]
+    fn test_apply_keeps_base() {
+        let mut stack = ModifierStack::new();
+        stack.add("gloves", counter("grandma"), ModifierKind::Additive(50));
+
+        let mut grandma = CountingObject::from(
+            String::from("grandma"),
+            AtomicU64::new(1),
+            AtomicU64::new(10),
+            AtomicU64::new(80),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+            AtomicU64::new(100),
+        );
+
+        stack.apply([&mut grandma]);
+        stack.apply([&mut grandma]);
+        assert_eq!(130, grandma.effectiveness());
+        assert_eq!(80, grandma.base_effectiveness());
+
+        grandma.set_effectiveness(100);
+        stack.apply([&mut grandma]);
+        assert_eq!(150, grandma.effectiveness());
+    }
+
+    #[test]
+    fn test_buffs() {
+        let mut stack = ModifierStack::new();
+        stack.add_buff(
+            "frenzy",
+            ModifierTarget::Global,
+            ModifierKind::Multiplicative(700),
+            Duration::from_secs(77),
+        );
+        assert_eq!(700, stack.breakdown("farm", &BTreeMap::new()).effectiveness);
+
+        stack.elapse(Duration::from_secs(77));
+        assert!(stack.modifiers().is_empty());
+        assert_eq!(100, stack.breakdown("farm", &BTreeMap::new()).effectiveness);
+
+        stack.add("gloves", counter("farm"), ModifierKind::Additive(10));
+        assert!(stack.remove("gloves"));
+        assert!(!stack.remove("gloves"));
+    }
+}
@@ -0,0 +1,22 @@
+use overworld_progression::experience::ExperienceLevelUpTable;
+use overworld_progression::report::CurveReport;
+
+/// Prints an experience curve as a chart, Markdown and CSV
+///
+/// Usage: experience_curve_report [XP per hour] [milestone, ...]
+fn main() {
+    let mut args = std::env::args().skip(1);
+
+    let rate = args.next().and_then(|rate| rate.parse::<f64>().ok());
+    let mut milestones: Vec<u64> = args.filter_map(|arg| arg.parse().ok()).collect();
+    if milestones.is_empty() {
+        milestones = vec![25, 100, 1000, 2500, 10000];
+    }
+
+    let table = ExperienceLevelUpTable::named(String::from("Experience curve"), milestones);
+    let report = CurveReport::new(&table, rate.or(Some(500.)));
+
+    println!("{}", report.ascii_chart(40));
+    println!("{}", report.to_markdown());
+    println!("{}", report.to_csv());
+}
@@ -0,0 +1,231 @@
+use crate::experience::ExperienceSystem;
+
+/// The percentage of experience awarded from `min_difference` levels above the player and up
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelBracket {
+    pub min_difference: i64,
+    pub percentage: u64,
+}
+
+/// How experience scales with the difference between the source's level and the player's level
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LevelDifferenceCurve {
+    /// Experience is never scaled
+    Flat,
+    /// Every level of difference changes the percentage by `per_level`, clamped between min and max
+    Linear { per_level: u64, min: u64, max: u64 },
+    /// The bracket with the highest min_difference not above the difference is used,
+    /// differences below every bracket award nothing
+    Brackets(Vec<LevelBracket>),
+}
+
+impl LevelDifferenceCurve {
+    /// Gray, green, yellow, orange and red brackets, similar to those used by many MMOs
+    pub fn colored() -> Self {
+        Self::Brackets(vec![
+            LevelBracket {
+                min_difference: -5,
+                percentage: 50,
+            },
+            LevelBracket {
+                min_difference: -2,
+                percentage: 100,
+            },
+            LevelBracket {
+                min_difference: 3,
+                percentage: 110,
+            },
+            LevelBracket {
+                min_difference: 5,
+                percentage: 120,
+            },
+        ])
+    }
+
+    /// The percentage of experience awarded for a difference of source level - player level
+    pub fn percentage(&self, difference: i64) -> u64 {
+        match self {
+            LevelDifferenceCurve::Flat => 100,
+            LevelDifferenceCurve::Linear {
+                per_level,
+                min,
+                max,
+            } => {
+                let percentage =
+                    100i64.saturating_add(difference.saturating_mul(*per_level as i64));
+
+                (percentage.max(0) as u64).clamp(*min, (*max).max(*min))
+            }
+            LevelDifferenceCurve::Brackets(brackets) => brackets
+                .iter()
+                .filter(|bracket| bracket.min_difference <= difference)
+                .max_by_key(|bracket| bracket.min_difference)
+                .map(|bracket| bracket.percentage)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// How experience is divided between the members of a party
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartySplit {
+    /// Every member receives an equal share
+    Even,
+    /// Shares are weighted by each member's contribution (e.g. damage dealt)
+    ContributionWeighted,
+    /// Shares are weighted by each member's level
+    LevelWeighted,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartyMember {
+    pub level: u64,
+    pub contribution: u64,
+}
+
+/// Calculates experience awards scaled by level difference and split between party members
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceAward {
+    curve: LevelDifferenceCurve,
+    split: PartySplit,
+}
+
+impl ExperienceAward {
+    pub fn new(curve: LevelDifferenceCurve, split: PartySplit) -> Self {
+        Self { curve, split }
+    }
+
+    /// The experience a player of `player_level` receives from a source of `source_level`
+    pub fn calculate(&self, experience: u64, player_level: u64, source_level: u64) -> u64 {
+        let difference = source_level as i64 - player_level as i64;
+        let percentage = self.curve.percentage(difference);
+
+        (experience as u128 * percentage as u128 / 100).min(u64::MAX as u128) as u64
+    }
+
+    /// Like calculate, using the level of the experience system
+    pub fn calculate_for(
+        &self,
+        system: &ExperienceSystem,
+        experience: u64,
+        source_level: u64,
+    ) -> u64 {
+        self.calculate(experience, system.get_level(), source_level)
+    }
+
+    /// Splits experience between party members, after which each share is scaled by the
+    /// member's own level difference. Shares are returned in the same order as the members.
+    ///
+    /// Weighted splits fall back to an even split when every weight is zero.
+    pub fn split(&self, experience: u64, source_level: u64, members: &[PartyMember]) -> Vec<u64> {
+        let weights: Vec<u64> = members
+            .iter()
+            .map(|member| match self.split {
+                PartySplit::Even => 1,
+                PartySplit::ContributionWeighted => member.contribution,
+                PartySplit::LevelWeighted => member.level,
+            })
+            .collect();
+
+        let mut total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+        let even = total_weight == 0;
+        if even {
+            total_weight = members.len() as u128;
+        }
+
+        members
+            .iter()
+            .zip(weights)
+            .map(|(member, weight)| {
+                let weight = if even { 1 } else { weight };
+                let share = (experience as u128 * weight as u128 / total_weight) as u64;
+
+                self.calculate(share, member.level, source_level)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::award::{ExperienceAward, LevelDifferenceCurve, PartyMember, PartySplit};
+    use crate::experience::ExperienceSystem;
+
+    fn new_party() -> Vec<PartyMember> {
+        vec![
+            PartyMember {
+                level: 10,
+                contribution: 300,
+            },
+            PartyMember {
+                level: 10,
+                contribution: 100,
+            },
+            PartyMember {
+                level: 20,
+                contribution: 0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_colored_curve() {
+        let award = ExperienceAward::new(LevelDifferenceCurve::colored(), PartySplit::Even);
+
+        assert_eq!(0, award.calculate(100, 20, 10));
+        assert_eq!(50, award.calculate(100, 20, 15));
+        assert_eq!(100, award.calculate(100, 20, 20));
+        assert_eq!(110, award.calculate(100, 20, 23));
+        assert_eq!(120, award.calculate(100, 20, 40));
+    }
+
+    #[test]
+    fn test_linear_curve() {
+        let curve = LevelDifferenceCurve::Linear {
+            per_level: 10,
+            min: 20,
+            max: 150,
+        };
+
+        assert_eq!(100, curve.percentage(0));
+        assert_eq!(130, curve.percentage(3));
+        assert_eq!(150, curve.percentage(10));
+        assert_eq!(20, curve.percentage(-20));
+    }
+
+    #[test]
+    fn test_calculate_for_system() {
+        let award = ExperienceAward::new(LevelDifferenceCurve::colored(), PartySplit::Even);
+        let system = ExperienceSystem::simple(0, vec![100, 250, 500]);
+
+        assert_eq!(100, award.calculate_for(&system, 100, 1));
+        assert_eq!(120, award.calculate_for(&system, 100, 6));
+    }
+
+    #[test]
+    fn test_party_split() {
+        let flat = LevelDifferenceCurve::Flat;
+
+        let even = ExperienceAward::new(flat.clone(), PartySplit::Even);
+        assert_eq!(vec![100, 100, 100], even.split(300, 10, &new_party()));
+
+        let contribution = ExperienceAward::new(flat.clone(), PartySplit::ContributionWeighted);
+        assert_eq!(vec![300, 100, 0], contribution.split(400, 10, &new_party()));
+
+        let level = ExperienceAward::new(flat, PartySplit::LevelWeighted);
+        assert_eq!(vec![100, 100, 200], level.split(400, 10, &new_party()));
+    }
+
+    #[test]
+    fn test_party_split_scaled() {
+        let award = ExperienceAward::new(LevelDifferenceCurve::colored(), PartySplit::Even);
+
+        assert_eq!(vec![100, 100, 0], award.split(300, 10, &new_party()));
+        assert!(award.split(300, 10, &[]).is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+mod experience_award;
+
+pub use experience_award::{
+    ExperienceAward, LevelBracket, LevelDifferenceCurve, PartyMember, PartySplit,
+};
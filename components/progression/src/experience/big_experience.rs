@@ -0,0 +1,263 @@
+use overworld_incremental::number::BigNumber;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+
+/// Experience stored as mantissa * 10^exponent, for idle games that outgrow u64
+///
+/// A BigNumber that is never negative, subtraction saturates at zero.
+/// Precision is that of an f64, roughly 15 significant digits.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "BigNumber"))]
+pub struct BigExperience(BigNumber);
+
+impl BigExperience {
+    pub const ZERO: Self = Self(BigNumber::ZERO);
+
+    /// Creates a normalized number, non-finite and non-positive mantissas are treated as zero
+    pub fn new(mantissa: f64, exponent: i64) -> Self {
+        Self::from(BigNumber::new(mantissa, exponent))
+    }
+
+    pub fn mantissa(&self) -> f64 {
+        self.0.mantissa()
+    }
+
+    pub fn exponent(&self) -> i64 {
+        self.0.exponent()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The value as f64, infinite if it does not fit
+    pub fn to_f64(&self) -> f64 {
+        self.0.to_f64()
+    }
+}
+
+/// Negative numbers are treated as zero
+impl From<BigNumber> for BigExperience {
+    fn from(value: BigNumber) -> Self {
+        if value.is_negative() {
+            return Self::ZERO;
+        }
+
+        Self(value)
+    }
+}
+
+impl From<BigExperience> for BigNumber {
+    fn from(value: BigExperience) -> Self {
+        value.0
+    }
+}
+
+impl From<u64> for BigExperience {
+    fn from(value: u64) -> Self {
+        Self(BigNumber::from(value))
+    }
+}
+
+impl Add for BigExperience {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for BigExperience {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from(self.0 - other.0)
+    }
+}
+
+impl Display for BigExperience {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.exponent() < 15 {
+            write!(f, "{:.0}", self.to_f64())
+        } else {
+            write!(f, "{:.2}e{}", self.mantissa(), self.exponent())
+        }
+    }
+}
+
+/// An Experience-based Level Up Table with milestones beyond u64
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigExperienceLevelUpTable {
+    name: String,
+    milestones: Vec<BigExperience>,
+}
+
+impl BigExperienceLevelUpTable {
+    pub fn named(name: String, milestones: Vec<BigExperience>) -> Self {
+        let mut milestones = milestones;
+        milestones.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        Self { name, milestones }
+    }
+
+    pub fn new(milestones: Vec<BigExperience>) -> Self {
+        Self::named(String::from("Default"), milestones)
+    }
+
+    /// A table of `levels` milestones, starting at `first` and multiplied by `ratio` each level
+    pub fn geometric(first: BigExperience, ratio: f64, levels: usize) -> Self {
+        let mut milestones = Vec::with_capacity(levels);
+        let mut milestone = first;
+
+        for _ in 0..levels {
+            milestones.push(milestone);
+            milestone = BigExperience::new(milestone.mantissa() * ratio, milestone.exponent());
+        }
+
+        Self::new(milestones)
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn milestones(&self) -> &[BigExperience] {
+        &self.milestones
+    }
+
+    pub fn to_level(&self, experience: BigExperience) -> u64 {
+        self.reached(experience) as u64 + 1
+    }
+
+    pub fn get_next_milestone(&self, experience: BigExperience) -> Option<BigExperience> {
+        self.milestones.get(self.reached(experience)).copied()
+    }
+
+    pub fn is_maxed(&self, experience: BigExperience) -> bool {
+        self.reached(experience) == self.milestones.len()
+    }
+
+    fn reached(&self, experience: BigExperience) -> usize {
+        self.milestones
+            .partition_point(|milestone| *milestone <= experience)
+    }
+}
+
+/// An ExperienceSystem backed by BigExperience, it cannot overflow
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigExperienceSystem {
+    experience: BigExperience,
+    levels: BigExperienceLevelUpTable,
+}
+
+impl BigExperienceSystem {
+    pub fn new(experience: BigExperience, levels: BigExperienceLevelUpTable) -> Self {
+        Self { experience, levels }
+    }
+
+    pub fn get_level(&self) -> u64 {
+        self.levels.to_level(self.experience)
+    }
+
+    pub fn get_experience(&self) -> BigExperience {
+        self.experience
+    }
+
+    pub fn get_experience_remaining(&self) -> Option<BigExperience> {
+        self.get_next_milestone()
+            .map(|milestone| milestone - self.experience)
+    }
+
+    pub fn get_next_milestone(&self) -> Option<BigExperience> {
+        self.levels.get_next_milestone(self.experience)
+    }
+
+    pub fn add_experience(&mut self, amount: BigExperience) {
+        self.experience = self.experience + amount;
+    }
+
+    pub fn remove_experience(&mut self, amount: BigExperience) {
+        self.experience = self.experience - amount;
+    }
+
+    pub fn reset(&mut self) {
+        self.experience = BigExperience::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::{BigExperience, BigExperienceLevelUpTable, BigExperienceSystem};
+
+    #[test]
+    fn test_normalize() {
+        let value = BigExperience::new(1234., 30);
+
+        assert_eq!(1.234, value.mantissa());
+        assert_eq!(33, value.exponent());
+        assert!(BigExperience::new(-1., 5).is_zero());
+        assert_eq!(BigExperience::from(1000), BigExperience::new(1., 3));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = BigExperience::new(5., 30);
+        let b = BigExperience::new(5., 29);
+
+        assert_eq!(BigExperience::new(5.5, 30), a + b);
+        assert_eq!(BigExperience::new(4.5, 30), a - b);
+        assert_eq!(BigExperience::ZERO, b - a);
+        assert_eq!(a, a + BigExperience::from(1));
+        assert_eq!(
+            BigExperience::from(1500),
+            BigExperience::from(1000) + 500.into()
+        );
+    }
+
+    #[test]
+    fn test_ordering_and_display() {
+        assert!(BigExperience::new(1., 31) > BigExperience::new(9., 30));
+        assert!(BigExperience::ZERO < BigExperience::from(1));
+
+        assert_eq!("1500", BigExperience::from(1500).to_string());
+        assert_eq!("1.23e30", BigExperience::new(1.234, 30).to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        let value: BigExperience =
+            serde_json::from_str(r#"{"mantissa":1234.0,"exponent":30}"#).unwrap();
+        assert_eq!(BigExperience::new(1.234, 33), value);
+
+        let negative: BigExperience =
+            serde_json::from_str(r#"{"mantissa":-5.0,"exponent":30}"#).unwrap();
+        assert!(negative.is_zero());
+    }
+
+    #[test]
+    fn test_system() {
+        let table = BigExperienceLevelUpTable::geometric(BigExperience::new(1., 20), 1000., 5);
+        let mut system = BigExperienceSystem::new(BigExperience::ZERO, table);
+
+        assert_eq!(1, system.get_level());
+
+        system.add_experience(BigExperience::new(2., 26));
+        assert_eq!(4, system.get_level());
+        assert_eq!(
+            Some(BigExperience::new(1., 29)),
+            system.get_next_milestone()
+        );
+
+        system.add_experience(BigExperience::new(1., 40));
+        assert_eq!(6, system.get_level());
+        assert!(system.get_experience_remaining().is_none());
+
+        system.remove_experience(BigExperience::new(1., 50));
+        assert_eq!(1, system.get_level());
+    }
+}
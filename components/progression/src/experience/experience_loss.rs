@@ -0,0 +1,38 @@
+/// How an ExperienceSystem handles experience being removed, e.g. as a death penalty
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LossPolicy {
+    /// Experience is removed as requested, which may drop multiple levels
+    #[default]
+    Unrestricted,
+    /// Experience never drops below the milestone of the current level, levels are never lost
+    FloorAtMilestone,
+    /// At most this percentage of the current level's span is removed at once,
+    /// this can still drop a level when close to the milestone
+    PercentageOfLevel(u64),
+    /// Like FloorAtMilestone, but whatever could not be removed becomes debt,
+    /// which future gains pay off before they count towards the total
+    Debt,
+}
+
+/// The result of removing experience from an ExperienceSystem
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceLoss {
+    /// The experience that was requested to be removed
+    pub requested: u64,
+    /// The experience actually removed
+    pub lost: u64,
+    /// The experience added to the debt
+    pub debt: u64,
+    /// The level before the experience was removed
+    pub previous_level: u64,
+    /// The level after the experience was removed
+    pub level: u64,
+}
+
+impl ExperienceLoss {
+    pub fn levels_lost(&self) -> u64 {
+        self.previous_level.saturating_sub(self.level)
+    }
+}
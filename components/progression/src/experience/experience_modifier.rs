@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+/// A percentage bonus to experience gains, optionally limited in time
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceBonus {
+    /// Where the bonus comes from, e.g. "guild_perk" or "double_xp_weekend"
+    pub source: String,
+    /// Bonus percentage, 100 doubles the experience gained
+    pub percentage: u64,
+    /// Time left before the bonus expires, None if permanent
+    pub remaining: Option<Duration>,
+}
+
+/// A pool of rested experience that is consumed as experience is gained
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RestedExperience {
+    /// Rested experience available
+    pub pool: u64,
+    /// Maximum size of the pool
+    pub cap: u64,
+    /// Percentage of the base gain that is paid out of the pool as a bonus
+    pub percentage: u64,
+}
+
+/// Reduces gains once a threshold has been earned within a window of time
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiminishingReturns {
+    /// Experience that can be earned in a window before returns diminish
+    pub threshold: u64,
+    /// Percentage of the experience kept past the threshold
+    pub percentage: u64,
+    /// Length of the window, the accumulated experience resets when it passes
+    pub window: Duration,
+    elapsed: Duration,
+    accumulated: u64,
+}
+
+impl DiminishingReturns {
+    pub fn new(threshold: u64, percentage: u64, window: Duration) -> Self {
+        Self {
+            threshold,
+            percentage,
+            window,
+            elapsed: Duration::ZERO,
+            accumulated: 0,
+        }
+    }
+
+    /// Experience earned in the current window
+    pub fn accumulated(&self) -> u64 {
+        self.accumulated
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.accumulated = 0;
+    }
+}
+
+/// The breakdown of an experience gain after modifiers were applied
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifiedExperience {
+    /// Experience before any modifiers
+    pub base: u64,
+    /// Experience added by percentage bonuses
+    pub bonus: u64,
+    /// Experience taken away by diminishing returns
+    pub diminished: u64,
+    /// Experience paid out of the rested pool
+    pub rested: u64,
+    /// Experience used to pay off experience debt
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub debt_repaid: u64,
+    /// Experience actually gained
+    pub total: u64,
+}
+
+/// The modifier pipeline experience gains pass through
+///
+/// Gains are modified in order: percentage bonuses are summed and added,
+/// diminishing returns reduce whatever exceeds the threshold, and finally
+/// the rested pool pays out a bonus based on the base gain.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceModifiers {
+    bonuses: Vec<ExperienceBonus>,
+    rested: Option<RestedExperience>,
+    diminishing: Option<DiminishingReturns>,
+}
+
+impl ExperienceModifiers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bonuses.is_empty() && self.rested.is_none() && self.diminishing.is_none()
+    }
+
+    /// Adds a permanent percentage bonus
+    pub fn add_bonus(&mut self, source: String, percentage: u64) {
+        self.bonuses.push(ExperienceBonus {
+            source,
+            percentage,
+            remaining: None,
+        });
+    }
+
+    /// Adds a percentage bonus that expires after `duration` has elapsed
+    pub fn add_boost(&mut self, source: String, percentage: u64, duration: Duration) {
+        self.bonuses.push(ExperienceBonus {
+            source,
+            percentage,
+            remaining: Some(duration),
+        });
+    }
+
+    /// Removes every bonus from the given source, returns true if any were removed
+    pub fn remove_bonus(&mut self, source: &str) -> bool {
+        let before = self.bonuses.len();
+        self.bonuses.retain(|bonus| bonus.source != source);
+
+        before != self.bonuses.len()
+    }
+
+    pub fn bonuses(&self) -> &[ExperienceBonus] {
+        &self.bonuses
+    }
+
+    /// The summed percentage of every active bonus
+    pub fn bonus_percentage(&self) -> u64 {
+        self.bonuses
+            .iter()
+            .fold(0u64, |sum, bonus| sum.saturating_add(bonus.percentage))
+    }
+
+    pub fn set_rested(&mut self, rested: Option<RestedExperience>) {
+        self.rested = rested;
+    }
+
+    pub fn rested(&self) -> Option<&RestedExperience> {
+        self.rested.as_ref()
+    }
+
+    /// Adds experience to the rested pool up to its cap, returns the amount added
+    pub fn add_rested(&mut self, amount: u64) -> u64 {
+        match self.rested.as_mut() {
+            None => 0,
+            Some(rested) => {
+                let added = amount.min(rested.cap.saturating_sub(rested.pool));
+                rested.pool += added;
+                added
+            }
+        }
+    }
+
+    pub fn set_diminishing_returns(&mut self, diminishing: Option<DiminishingReturns>) {
+        self.diminishing = diminishing;
+    }
+
+    pub fn diminishing_returns(&self) -> Option<&DiminishingReturns> {
+        self.diminishing.as_ref()
+    }
+
+    /// Advances time: expires timed boosts and rolls over the diminishing returns window
+    pub fn elapse(&mut self, duration: Duration) {
+        self.bonuses.retain_mut(|bonus| match bonus.remaining {
+            None => true,
+            Some(remaining) => {
+                bonus.remaining = Some(remaining.saturating_sub(duration));
+                remaining > duration
+            }
+        });
+
+        if let Some(diminishing) = self.diminishing.as_mut() {
+            diminishing.elapsed += duration;
+
+            if diminishing.elapsed >= diminishing.window {
+                diminishing.reset();
+            }
+        }
+    }
+
+    /// Runs an experience gain through the pipeline, consuming rested experience
+    pub fn apply(&mut self, base: u64) -> ModifiedExperience {
+        let bonus = percentage_of(base, self.bonus_percentage());
+        let mut total = base.saturating_add(bonus);
+
+        let mut diminished = 0;
+        if let Some(diminishing) = self.diminishing.as_mut() {
+            let allowance = diminishing
+                .threshold
+                .saturating_sub(diminishing.accumulated);
+
+            if total > allowance {
+                let excess = total - allowance;
+                diminished = excess - percentage_of(excess, diminishing.percentage.min(100));
+                total -= diminished;
+            }
+
+            diminishing.accumulated = diminishing.accumulated.saturating_add(total);
+        }
+
+        let mut rested = 0;
+        if let Some(pool) = self.rested.as_mut() {
+            rested = percentage_of(base, pool.percentage).min(pool.pool);
+            pool.pool -= rested;
+            total = total.saturating_add(rested);
+        }
+
+        ModifiedExperience {
+            base,
+            bonus,
+            diminished,
+            rested,
+            debt_repaid: 0,
+            total,
+        }
+    }
+}
+
+fn percentage_of(amount: u64, percentage: u64) -> u64 {
+    (amount as u128 * percentage as u128 / 100).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::{DiminishingReturns, ExperienceModifiers, RestedExperience};
+    use std::time::Duration;
+
+    #[test]
+    fn test_no_modifiers() {
+        let mut modifiers = ExperienceModifiers::new();
+
+        assert!(modifiers.is_empty());
+        assert_eq!(100, modifiers.apply(100).total);
+    }
+
+    #[test]
+    fn test_bonuses_stack() {
+        let mut modifiers = ExperienceModifiers::new();
+        modifiers.add_bonus(String::from("guild"), 10);
+        modifiers.add_boost(
+            String::from("weekend"),
+            100,
+            Duration::from_secs(60 * 60 * 48),
+        );
+
+        let gain = modifiers.apply(100);
+        assert_eq!(110, gain.bonus);
+        assert_eq!(210, gain.total);
+
+        modifiers.elapse(Duration::from_secs(60 * 60 * 48));
+        assert_eq!(1, modifiers.bonuses().len());
+        assert_eq!(110, modifiers.apply(100).total);
+
+        assert!(modifiers.remove_bonus("guild"));
+        assert_eq!(100, modifiers.apply(100).total);
+    }
+
+    #[test]
+    fn test_rested_experience() {
+        let mut modifiers = ExperienceModifiers::new();
+        modifiers.set_rested(Some(RestedExperience {
+            pool: 0,
+            cap: 150,
+            percentage: 100,
+        }));
+
+        assert_eq!(150, modifiers.add_rested(500));
+
+        let gain = modifiers.apply(100);
+        assert_eq!(100, gain.rested);
+        assert_eq!(200, gain.total);
+
+        let gain = modifiers.apply(100);
+        assert_eq!(50, gain.rested);
+        assert_eq!(0, modifiers.rested().unwrap().pool);
+    }
+
+    #[test]
+    fn test_diminishing_returns() {
+        let mut modifiers = ExperienceModifiers::new();
+        modifiers.set_diminishing_returns(Some(DiminishingReturns::new(
+            150,
+            50,
+            Duration::from_secs(3600),
+        )));
+
+        assert_eq!(100, modifiers.apply(100).total);
+
+        let gain = modifiers.apply(100);
+        assert_eq!(25, gain.diminished);
+        assert_eq!(75, gain.total);
+
+        modifiers.elapse(Duration::from_secs(3600));
+        assert_eq!(0, modifiers.diminishing_returns().unwrap().accumulated());
+        assert_eq!(100, modifiers.apply(100).total);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::experience::{ExperienceLevelUpTable, ExperienceTracker};
+use crate::experience::experience_tracker::now;
+use crate::experience::{
+    ExperienceLevelUpTable, ExperienceLoss, ExperienceModifiers, ExperienceTracker, LossPolicy,
+    ModifiedExperience,
+};
 use crate::LevelUpTable;
 
 #[derive(Debug, Clone)]
@@ -6,6 +10,31 @@ use crate::LevelUpTable;
 pub struct ExperienceSystem {
     tracker: ExperienceTracker,
     levels: ExperienceLevelUpTable,
+    #[cfg_attr(feature = "serde", serde(default))]
+    modifiers: ExperienceModifiers,
+    #[cfg_attr(feature = "serde", serde(default))]
+    loss_policy: LossPolicy,
+    /// Experience that must be repaid before gains count towards the total
+    #[cfg_attr(feature = "serde", serde(default))]
+    debt: u64,
+}
+
+/// The result of gaining experience through an ExperienceSystem
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceGain {
+    /// The breakdown of the modifiers that were applied
+    pub experience: ModifiedExperience,
+    /// The level before the experience was gained
+    pub previous_level: u64,
+    /// The level after the experience was gained
+    pub level: u64,
+}
+
+impl ExperienceGain {
+    pub fn levels_gained(&self) -> u64 {
+        self.level.saturating_sub(self.previous_level)
+    }
 }
 
 impl ExperienceSystem {
@@ -17,7 +46,13 @@ impl ExperienceSystem {
     }
 
     pub fn new(tracker: ExperienceTracker, levels: ExperienceLevelUpTable) -> Self {
-        Self { tracker, levels }
+        Self {
+            tracker,
+            levels,
+            modifiers: ExperienceModifiers::new(),
+            loss_policy: LossPolicy::default(),
+            debt: 0,
+        }
     }
 
     pub fn get_level(&self) -> u64 {
@@ -38,25 +73,173 @@ impl ExperienceSystem {
         self.levels.get_next_milestone(self.tracker.get())
     }
 
+    pub fn modifiers(&self) -> &ExperienceModifiers {
+        &self.modifiers
+    }
+
+    pub fn modifiers_mut(&mut self) -> &mut ExperienceModifiers {
+        &mut self.modifiers
+    }
+
+    /// Adds experience through the modifiers, saturating at u64::MAX
     pub fn add_experience(&mut self, amount: u64) {
-        self.tracker.add(amount);
+        self.gain_experience(amount);
+    }
+
+    /// Adds experience through the modifiers unless it would overflow,
+    /// returns the new total or None on overflow, in which case nothing changes
+    pub fn checked_add_experience(&mut self, amount: u64) -> Option<u64> {
+        // Modifiers keep state (rested pool, diminishing returns), so preview on a copy
+        let total = self.modifiers.clone().apply(amount).total;
+        self.tracker
+            .get()
+            .checked_add(total.saturating_sub(self.debt))?;
+
+        self.gain_experience(amount);
+        Some(self.tracker.get())
+    }
+
+    /// Adds experience after running it through the modifiers,
+    /// returns the breakdown of what was gained.
+    pub fn gain_experience(&mut self, amount: u64) -> ExperienceGain {
+        self.gain(amount, None, None)
+    }
+
+    /// Like gain_experience, but tags the gain with a source in the session history
+    pub fn gain_experience_from(&mut self, amount: u64, source: &str) -> ExperienceGain {
+        self.gain(amount, Some(source), None)
+    }
+
+    /// Like gain_experience_from, but recorded at the given timestamp instead of now
+    pub fn gain_experience_at(
+        &mut self,
+        amount: u64,
+        source: Option<&str>,
+        timestamp: u64,
+    ) -> ExperienceGain {
+        self.gain(amount, source, Some(timestamp))
     }
 
     pub fn remove_experience(&mut self, amount: u64) {
-        self.tracker.remove(amount);
+        self.lose_experience(amount);
+    }
+
+    /// Removes experience according to the loss policy, returns what was lost
+    pub fn lose_experience(&mut self, amount: u64) -> ExperienceLoss {
+        self.lose(amount, None)
+    }
+
+    /// Like lose_experience, but tags the loss with a source in the session history
+    pub fn lose_experience_from(&mut self, amount: u64, source: &str) -> ExperienceLoss {
+        self.lose(amount, Some(source))
+    }
+
+    /// The most experience the loss policy allows removing at once, without going into debt
+    pub fn removable_experience(&self) -> u64 {
+        let experience = self.tracker.get();
+        let milestone = self.levels.get_current_milestone(experience);
+
+        match self.loss_policy {
+            LossPolicy::Unrestricted => experience,
+            LossPolicy::FloorAtMilestone | LossPolicy::Debt => experience - milestone,
+            LossPolicy::PercentageOfLevel(percentage) => {
+                let span = self.get_next_milestone().unwrap_or(experience) - milestone;
+                let cap = (span as u128 * percentage as u128 / 100) as u64;
+
+                cap.min(experience)
+            }
+        }
+    }
+
+    fn lose(&mut self, amount: u64, source: Option<&str>) -> ExperienceLoss {
+        let previous_level = self.get_level();
+        let lost = amount.min(self.removable_experience());
+        let debt = match self.loss_policy {
+            LossPolicy::Debt => amount - lost,
+            _ => 0,
+        };
+
+        self.tracker.remove_at(lost, source, now());
+        self.debt = self.debt.saturating_add(debt);
+
+        ExperienceLoss {
+            requested: amount,
+            lost,
+            debt,
+            previous_level,
+            level: self.get_level(),
+        }
+    }
+
+    pub fn loss_policy(&self) -> LossPolicy {
+        self.loss_policy
+    }
+
+    pub fn set_loss_policy(&mut self, loss_policy: LossPolicy) {
+        self.loss_policy = loss_policy;
+    }
+
+    /// Experience that must be repaid before gains count towards the total
+    pub fn get_debt(&self) -> u64 {
+        self.debt
+    }
+
+    pub fn tracker(&self) -> &ExperienceTracker {
+        &self.tracker
+    }
+
+    pub fn tracker_mut(&mut self) -> &mut ExperienceTracker {
+        &mut self.tracker
+    }
+
+    /// Resets the experience and debt back to zero, keeping the level up table intact.
+    pub fn reset(&mut self) {
+        self.tracker.set(0);
+        self.debt = 0;
+    }
+
+    /// Gains modified experience, recorded now unless a timestamp is given
+    fn gain(
+        &mut self,
+        amount: u64,
+        source: Option<&str>,
+        timestamp: Option<u64>,
+    ) -> ExperienceGain {
+        let previous_level = self.get_level();
+        let experience = self.modify(amount);
+
+        self.tracker
+            .add_modified_at(experience.clone(), source, timestamp.unwrap_or_else(now));
+
+        ExperienceGain {
+            experience,
+            previous_level,
+            level: self.get_level(),
+        }
+    }
+
+    /// Runs the gain through the modifiers, and pays off debt from the result
+    fn modify(&mut self, amount: u64) -> ModifiedExperience {
+        let mut experience = self.modifiers.apply(amount);
+
+        experience.debt_repaid = experience.total.min(self.debt);
+        experience.total -= experience.debt_repaid;
+        self.debt -= experience.debt_repaid;
+
+        experience
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::experience::experience_system::ExperienceSystem;
-    use crate::experience::{ExperienceLevelUpTable, ExperienceTracker};
+    use crate::experience::{ExperienceLevelUpTable, ExperienceTracker, LossPolicy};
 
     fn new_experience_level_up_system() -> ExperienceSystem {
         let tracker = ExperienceTracker::new();
         let levels = ExperienceLevelUpTable::named(String::from("Test"), vec![100, 250, 500]);
 
-        ExperienceSystem { tracker, levels }
+        ExperienceSystem::new(tracker, levels)
     }
 
     #[test]
@@ -103,4 +286,92 @@ mod tests {
         assert!(system.get_experience_remaining().is_none());
         assert!(system.get_next_milestone().is_none());
     }
+
+    #[test]
+    fn test_gain_experience_with_modifiers() {
+        let mut system = new_experience_level_up_system();
+        system.modifiers_mut().add_bonus(String::from("Test"), 50);
+
+        let gain = system.gain_experience(200);
+        assert_eq!(100, gain.experience.bonus);
+        assert_eq!(300, gain.experience.total);
+        assert_eq!(2, gain.levels_gained());
+        assert_eq!(300, system.get_experience());
+    }
+
+    #[test]
+    fn test_checked_add_experience() {
+        let mut system = new_experience_level_up_system();
+        system.modifiers_mut().add_bonus(String::from("Test"), 50);
+
+        assert_eq!(Some(150), system.checked_add_experience(100));
+        // Fits without the bonus, but not with it
+        assert_eq!(None, system.checked_add_experience(u64::MAX / 4 * 3));
+        assert_eq!(150, system.get_experience());
+        assert_eq!(
+            Some(150 + u64::MAX / 2 + u64::MAX / 4),
+            system.checked_add_experience(u64::MAX / 2)
+        );
+    }
+
+    #[test]
+    fn test_gain_experience_from() {
+        let mut system = new_experience_level_up_system();
+
+        system.gain_experience_from(100, "quest:dragon");
+
+        let totals = system.tracker().source_totals("quest:dragon").unwrap();
+        assert_eq!(100, totals.gained);
+    }
+
+    #[test]
+    fn test_loss_unrestricted() {
+        let mut system = new_experience_level_up_system();
+        system.add_experience(300);
+
+        let loss = system.lose_experience(250);
+        assert_eq!(250, loss.lost);
+        assert_eq!(2, loss.levels_lost());
+    }
+
+    #[test]
+    fn test_loss_floor_at_milestone() {
+        let mut system = new_experience_level_up_system();
+        system.set_loss_policy(LossPolicy::FloorAtMilestone);
+        system.add_experience(300);
+
+        let loss = system.lose_experience(250);
+        assert_eq!(50, loss.lost);
+        assert_eq!(0, loss.levels_lost());
+        assert_eq!(250, system.get_experience());
+    }
+
+    #[test]
+    fn test_loss_percentage_of_level() {
+        let mut system = new_experience_level_up_system();
+        system.set_loss_policy(LossPolicy::PercentageOfLevel(10));
+        system.add_experience(260);
+
+        let loss = system.lose_experience(1000);
+        assert_eq!(25, loss.lost);
+        assert_eq!(1, loss.levels_lost());
+    }
+
+    #[test]
+    fn test_loss_debt() {
+        let mut system = new_experience_level_up_system();
+        system.set_loss_policy(LossPolicy::Debt);
+        system.add_experience(150);
+
+        let loss = system.lose_experience(100);
+        assert_eq!(50, loss.lost);
+        assert_eq!(50, loss.debt);
+        assert_eq!(50, system.get_debt());
+
+        let gain = system.gain_experience(80);
+        assert_eq!(50, gain.experience.debt_repaid);
+        assert_eq!(30, gain.experience.total);
+        assert_eq!(130, system.get_experience());
+        assert_eq!(0, system.get_debt());
+    }
 }
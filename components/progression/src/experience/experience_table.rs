@@ -1,14 +1,56 @@
 use crate::LevelUpTable;
+use std::fmt::{Display, Formatter};
 use std::ops::Index;
 
 /// A traditional Experience-based Level Up Table
+///
+/// Every player starts at level 1 with a base of 0 experience, which is not a milestone.
+/// Reaching the first milestone grants level 2, the second level 3, and so on.
+/// An empty table keeps everyone at level 1 and is considered maxed.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "TableData"))]
 pub struct ExperienceLevelUpTable {
     name: String,
     milestones: Vec<u64>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LevelUpTableError {
+    /// The table has no milestones
+    Empty,
+    /// The milestone occurs more than once
+    DuplicateMilestone(u64),
+}
+
+impl Display for LevelUpTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelUpTableError::Empty => write!(f, "Level up table has no milestones"),
+            LevelUpTableError::DuplicateMilestone(milestone) => {
+                write!(f, "Milestone {} occurs more than once", milestone)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelUpTableError {}
+
+/// Deserialized tables go through `named` to restore the sorting lookups rely on
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TableData {
+    name: String,
+    milestones: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<TableData> for ExperienceLevelUpTable {
+    fn from(data: TableData) -> Self {
+        Self::named(data.name, data.milestones)
+    }
+}
+
 impl LevelUpTable for ExperienceLevelUpTable {
     fn name(&self) -> String {
         self.name.clone()
@@ -22,11 +64,7 @@ impl LevelUpTable for ExperienceLevelUpTable {
     }
 
     fn get_previous_milestone(&self, experience_points: u64) -> Option<u64> {
-        if *self.milestones.first().unwrap() > experience_points {
-            return None;
-        }
-
-        let pointer = self.seek(experience_points).unwrap();
+        let pointer = self.seek(experience_points)?;
 
         if pointer == 0 {
             return Some(*self.milestones.index(pointer));
@@ -60,11 +98,15 @@ impl LevelUpTable for ExperienceLevelUpTable {
     }
 
     fn is_maxed(&self, experience_points: u64) -> bool {
-        experience_points >= *self.milestones.last().unwrap()
+        match self.milestones.last() {
+            None => true,
+            Some(last) => experience_points >= *last,
+        }
     }
 }
 
 impl ExperienceLevelUpTable {
+    /// Creates a table from unsorted milestones, use `try_named` to reject invalid tables
     pub fn named(name: String, milestones: Vec<u64>) -> Self {
         // Ensure sorting
         let mut ms = milestones.clone();
@@ -80,22 +122,46 @@ impl ExperienceLevelUpTable {
         Self::named(String::from("Default"), milestones)
     }
 
-    fn seek(&self, experience_points: u64) -> Option<usize> {
-        let mut pointer = None;
+    /// Creates a table from unsorted milestones, rejecting empty tables and duplicate milestones
+    pub fn try_named(name: String, milestones: Vec<u64>) -> Result<Self, LevelUpTableError> {
+        let table = Self::named(name, milestones);
 
-        for (index, milestone) in self.milestones.iter().enumerate() {
-            if experience_points >= *milestone {
-                pointer = Some(index);
-            }
+        if table.milestones.is_empty() {
+            return Err(LevelUpTableError::Empty);
         }
 
-        pointer
+        if let Some(pair) = table.milestones.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(LevelUpTableError::DuplicateMilestone(pair[0]));
+        }
+
+        Ok(table)
+    }
+
+    pub fn try_new(milestones: Vec<u64>) -> Result<Self, LevelUpTableError> {
+        Self::try_named(String::from("Default"), milestones)
+    }
+
+    /// The sorted milestones of the table
+    pub fn milestones(&self) -> &[u64] {
+        &self.milestones
+    }
+
+    /// The level reached at the last milestone
+    pub fn max_level(&self) -> u64 {
+        self.milestones.len() as u64 + 1
+    }
+
+    /// Index of the last milestone reached, found with a binary search
+    fn seek(&self, experience_points: u64) -> Option<usize> {
+        self.milestones
+            .partition_point(|milestone| *milestone <= experience_points)
+            .checked_sub(1)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::experience::ExperienceLevelUpTable;
+    use crate::experience::{ExperienceLevelUpTable, LevelUpTableError};
     use crate::LevelUpTable;
 
     fn new_experience_level_up_table() -> ExperienceLevelUpTable {
@@ -153,4 +219,39 @@ mod tests {
         assert_eq!(10, table.get_next_milestone(9).unwrap());
         assert_eq!(50, table.get_next_milestone(10).unwrap());
     }
+
+    #[test]
+    fn test_try_new() {
+        assert!(ExperienceLevelUpTable::try_new(vec![50, 10]).is_ok());
+        assert_eq!(
+            LevelUpTableError::Empty,
+            ExperienceLevelUpTable::try_new(vec![]).unwrap_err()
+        );
+        assert_eq!(
+            LevelUpTableError::DuplicateMilestone(10),
+            ExperienceLevelUpTable::try_new(vec![10, 50, 10]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let table = ExperienceLevelUpTable::new(vec![]);
+
+        assert_eq!(1, table.to_level(100));
+        assert!(table.is_maxed(0));
+        assert!(table.get_previous_milestone(100).is_none());
+        assert!(table.get_next_milestone(100).is_none());
+        assert_eq!(0, table.get_current_milestone(100));
+    }
+
+    #[test]
+    fn test_large_table() {
+        let table = ExperienceLevelUpTable::new((1..=10_000).map(|level| level * 10).collect());
+
+        assert_eq!(10_001, table.max_level());
+        assert_eq!(1, table.to_level(9));
+        assert_eq!(501, table.to_level(5_005));
+        assert_eq!(10_001, table.to_level(u64::MAX));
+        assert_eq!(5_010, table.get_next_milestone(5_005).unwrap());
+    }
 }
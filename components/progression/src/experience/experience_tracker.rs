@@ -1,37 +1,155 @@
+use crate::experience::ModifiedExperience;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether experience was gained or lost
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExperienceChange {
+    Gain,
+    Loss,
+}
+
+/// A single gain or loss in the session history
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceEntry {
+    pub change: ExperienceChange,
+    /// The experience that was gained or lost
+    pub amount: u64,
+    /// Where the experience came from, e.g. "quest:dragon" or "kill:goblin"
+    pub source: Option<String>,
+    /// Increments by one for every entry recorded this session
+    pub sequence: u64,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    /// The experience total after this entry was applied
+    pub total: u64,
+    /// The breakdown of the gain if it went through experience modifiers
+    pub modifiers: Option<ModifiedExperience>,
+}
+
+/// Aggregated experience for a single source over the session
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceTotals {
+    pub gained: u64,
+    pub lost: u64,
+    /// Number of entries recorded for this source
+    pub entries: u64,
+}
+
 /// A traditional experience-based XP Tracker
+///
+/// Aggregates (per source totals, session gains and losses) are kept for the entire session,
+/// even when the retention limit drops old entries from the history.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "TrackerData"))]
 pub struct ExperienceTracker {
     experience_points: u64,
+    history: VecDeque<ExperienceEntry>,
+    sources: BTreeMap<String, SourceTotals>,
+    session_gained: u64,
+    session_lost: u64,
+    sequence: u64,
+    session_start: u64,
+    session_end: Option<u64>,
+    /// The maximum amount of entries kept in the history, None for unlimited
+    retention: Option<usize>,
+    tracking: bool,
+}
+
+/// Deserialized trackers go through `TrackerData` so saves from before the session history,
+/// which only kept `session_gains` and `session_losses`, still load
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TrackerData {
+    experience_points: u64,
+    #[serde(default)]
+    history: VecDeque<ExperienceEntry>,
+    #[serde(default)]
+    sources: BTreeMap<String, SourceTotals>,
+    #[serde(default)]
+    session_gained: u64,
+    #[serde(default)]
+    session_lost: u64,
+    #[serde(default)]
+    sequence: u64,
+    #[serde(default = "now")]
+    session_start: u64,
+    #[serde(default)]
+    session_end: Option<u64>,
+    #[serde(default)]
+    retention: Option<usize>,
+    tracking: bool,
+    #[serde(default)]
     session_gains: Vec<u64>,
+    #[serde(default)]
     session_losses: Vec<u64>,
-    tracking: bool,
+}
+
+#[cfg(feature = "serde")]
+impl From<TrackerData> for ExperienceTracker {
+    fn from(data: TrackerData) -> Self {
+        let mut tracker = Self {
+            experience_points: data.experience_points,
+            history: data.history,
+            sources: data.sources,
+            session_gained: data.session_gained,
+            session_lost: data.session_lost,
+            sequence: data.sequence,
+            session_start: data.session_start,
+            session_end: data.session_end,
+            retention: data.retention,
+            tracking: data.tracking,
+        };
+
+        let timestamp = tracker.session_start;
+        for gain in data.session_gains {
+            tracker.record(ExperienceChange::Gain, gain, None, timestamp, None);
+        }
+        for loss in data.session_losses {
+            tracker.record(ExperienceChange::Loss, loss, None, timestamp, None);
+        }
+
+        tracker
+    }
 }
 
 impl ExperienceTracker {
     pub fn tracked(experience_points: u64, gains: Vec<u64>, losses: Vec<u64>) -> Self {
-        Self {
-            experience_points,
-            session_gains: gains,
-            session_losses: losses,
-            tracking: true,
+        let mut tracker = Self::from(experience_points);
+        let timestamp = tracker.session_start;
+
+        for gain in gains {
+            tracker.record(ExperienceChange::Gain, gain, None, timestamp, None);
+        }
+        for loss in losses {
+            tracker.record(ExperienceChange::Loss, loss, None, timestamp, None);
         }
+
+        tracker
     }
 
     pub fn untracked(experience_points: u64) -> Self {
         Self {
-            experience_points,
-            session_gains: vec![],
-            session_losses: vec![],
             tracking: false,
+            ..Self::from(experience_points)
         }
     }
 
     pub fn from(experience_points: u64) -> Self {
         Self {
             experience_points,
-            session_gains: Vec::new(),
-            session_losses: Vec::new(),
+            history: VecDeque::new(),
+            sources: BTreeMap::new(),
+            session_gained: 0,
+            session_lost: 0,
+            sequence: 0,
+            session_start: now(),
+            session_end: None,
+            retention: None,
             tracking: true,
         }
     }
@@ -40,34 +158,168 @@ impl ExperienceTracker {
         Self::from(0)
     }
 
+    /// Limits the history to the most recent `limit` entries
+    pub fn with_retention(mut self, limit: usize) -> Self {
+        self.set_retention(Some(limit));
+        self
+    }
+
     pub fn get(&self) -> u64 {
         self.experience_points
     }
 
     pub fn add(&mut self, experience_points: u64) {
-        if self.tracking {
-            self.session_gains.push(experience_points);
-        }
-        self.experience_points += experience_points;
+        self.add_at(experience_points, None, now());
+    }
+
+    pub fn add_from(&mut self, experience_points: u64, source: &str) {
+        self.add_at(experience_points, Some(source), now());
+    }
+
+    /// Adds experience, saturating at u64::MAX. Only the experience actually added is recorded.
+    pub fn add_at(&mut self, experience_points: u64, source: Option<&str>, timestamp: u64) {
+        let added = self.saturate(experience_points);
+        self.record(ExperienceChange::Gain, added, source, timestamp, None);
+    }
+
+    /// Adds experience unless it would overflow, returns the new total or None on overflow
+    pub fn checked_add(&mut self, experience_points: u64) -> Option<u64> {
+        self.experience_points.checked_add(experience_points)?;
+        self.add(experience_points);
+
+        Some(self.experience_points)
+    }
+
+    /// Adds the total of a modified gain, keeping its breakdown in the session history
+    pub fn add_modified(&mut self, experience: ModifiedExperience) {
+        self.add_modified_at(experience, None, now());
+    }
+
+    pub fn add_modified_from(&mut self, experience: ModifiedExperience, source: &str) {
+        self.add_modified_at(experience, Some(source), now());
+    }
+
+    pub fn add_modified_at(
+        &mut self,
+        experience: ModifiedExperience,
+        source: Option<&str>,
+        timestamp: u64,
+    ) {
+        let added = self.saturate(experience.total);
+        self.record(
+            ExperienceChange::Gain,
+            added,
+            source,
+            timestamp,
+            Some(experience),
+        );
     }
 
     pub fn remove(&mut self, experience_points: u64) {
-        if experience_points > self.experience_points {
-            if self.tracking {
-                self.session_losses.push(self.experience_points);
-            }
-            self.experience_points = 0;
-            return;
-        }
+        self.remove_at(experience_points, None, now());
+    }
 
-        if self.tracking {
-            self.session_losses.push(experience_points);
-        }
-        self.experience_points -= experience_points;
+    pub fn remove_from(&mut self, experience_points: u64, source: &str) {
+        self.remove_at(experience_points, Some(source), now());
+    }
+
+    pub fn remove_at(&mut self, experience_points: u64, source: Option<&str>, timestamp: u64) {
+        let removed = experience_points.min(self.experience_points);
+
+        self.experience_points -= removed;
+        self.record(ExperienceChange::Loss, removed, source, timestamp, None);
     }
 
+    /// Gains and losses that are still retained in the history
     pub fn session_history(&self) -> (Vec<u64>, Vec<u64>) {
-        (self.session_gains.clone(), self.session_losses.clone())
+        let amounts = |change| {
+            self.history
+                .iter()
+                .filter(|entry| entry.change == change)
+                .map(|entry| entry.amount)
+                .collect()
+        };
+
+        (
+            amounts(ExperienceChange::Gain),
+            amounts(ExperienceChange::Loss),
+        )
+    }
+
+    /// Every entry still retained in the history, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &ExperienceEntry> {
+        self.history.iter()
+    }
+
+    /// The breakdown of every modified gain still retained in the history
+    pub fn modifier_history(&self) -> Vec<ModifiedExperience> {
+        self.history
+            .iter()
+            .filter_map(|entry| entry.modifiers.clone())
+            .collect()
+    }
+
+    pub fn source_totals(&self, source: &str) -> Option<&SourceTotals> {
+        self.sources.get(source)
+    }
+
+    pub fn sources(&self) -> &BTreeMap<String, SourceTotals> {
+        &self.sources
+    }
+
+    /// Experience gained this session, including entries dropped by retention
+    pub fn session_gained(&self) -> u64 {
+        self.session_gained
+    }
+
+    /// Experience lost this session, including entries dropped by retention
+    pub fn session_lost(&self) -> u64 {
+        self.session_lost
+    }
+
+    /// Clears the history and aggregates, and starts a new session at `timestamp`
+    pub fn start_session(&mut self, timestamp: u64) {
+        self.history.clear();
+        self.sources.clear();
+        self.session_gained = 0;
+        self.session_lost = 0;
+        self.sequence = 0;
+        self.session_start = timestamp;
+        self.session_end = None;
+    }
+
+    pub fn end_session(&mut self, timestamp: u64) {
+        self.session_end = Some(timestamp);
+    }
+
+    pub fn session_start(&self) -> u64 {
+        self.session_start
+    }
+
+    pub fn session_end(&self) -> Option<u64> {
+        self.session_end
+    }
+
+    /// Experience gained per hour between the session start and `timestamp`,
+    /// or the session end if the session has ended.
+    pub fn experience_per_hour(&self, timestamp: u64) -> f64 {
+        let end = self.session_end.unwrap_or(timestamp);
+        let seconds = end.saturating_sub(self.session_start);
+
+        if seconds == 0 {
+            return 0.;
+        }
+
+        self.session_gained as f64 * 3600. / seconds as f64
+    }
+
+    pub fn retention(&self) -> Option<usize> {
+        self.retention
+    }
+
+    pub fn set_retention(&mut self, retention: Option<usize>) {
+        self.retention = retention;
+        self.truncate();
     }
 
     pub fn is_tracking(&self) -> bool {
@@ -77,6 +329,64 @@ impl ExperienceTracker {
     pub fn set(&mut self, experience_points: u64) {
         self.experience_points = experience_points;
     }
+
+    /// Adds as much experience as fits, returns the experience added
+    fn saturate(&mut self, experience_points: u64) -> u64 {
+        let before = self.experience_points;
+        self.experience_points = before.saturating_add(experience_points);
+
+        self.experience_points - before
+    }
+
+    fn record(
+        &mut self,
+        change: ExperienceChange,
+        amount: u64,
+        source: Option<&str>,
+        timestamp: u64,
+        modifiers: Option<ModifiedExperience>,
+    ) {
+        if !self.tracking {
+            return;
+        }
+
+        match change {
+            ExperienceChange::Gain => {
+                self.session_gained = self.session_gained.saturating_add(amount)
+            }
+            ExperienceChange::Loss => self.session_lost = self.session_lost.saturating_add(amount),
+        }
+
+        if let Some(source) = source {
+            let totals = self.sources.entry(source.to_string()).or_default();
+            totals.entries += 1;
+
+            match change {
+                ExperienceChange::Gain => totals.gained = totals.gained.saturating_add(amount),
+                ExperienceChange::Loss => totals.lost = totals.lost.saturating_add(amount),
+            }
+        }
+
+        self.sequence += 1;
+        self.history.push_back(ExperienceEntry {
+            change,
+            amount,
+            source: source.map(String::from),
+            sequence: self.sequence,
+            timestamp,
+            total: self.experience_points,
+            modifiers,
+        });
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        if let Some(limit) = self.retention {
+            while self.history.len() > limit {
+                self.history.pop_front();
+            }
+        }
+    }
 }
 
 impl Default for ExperienceTracker {
@@ -85,9 +395,16 @@ impl Default for ExperienceTracker {
     }
 }
 
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::experience::ExperienceTracker;
+    use crate::experience::{ExperienceChange, ExperienceTracker, ModifiedExperience};
 
     #[test]
     fn test_experience_tracker() {
@@ -114,6 +431,20 @@ mod tests {
         assert_eq!(experience_tracker.get(), 0);
     }
 
+    #[test]
+    fn test_experience_tracker_overflow() {
+        let mut experience_tracker = ExperienceTracker::from(u64::MAX - 10);
+
+        assert!(experience_tracker.checked_add(11).is_none());
+        assert_eq!(u64::MAX - 10, experience_tracker.get());
+        assert_eq!(Some(u64::MAX), experience_tracker.checked_add(10));
+
+        experience_tracker.set(u64::MAX - 10);
+        experience_tracker.add(100);
+        assert_eq!(u64::MAX, experience_tracker.get());
+        assert_eq!(vec![10, 10], experience_tracker.session_history().0);
+    }
+
     #[test]
     fn test_untracked() {
         let mut experience_tracker = ExperienceTracker::untracked(0);
@@ -137,4 +468,126 @@ mod tests {
         assert!(!gains.is_empty());
         assert!(!losses.is_empty());
     }
+
+    #[test]
+    fn test_modifier_history() {
+        let mut experience_tracker = ExperienceTracker::new();
+
+        experience_tracker.add_modified(ModifiedExperience {
+            base: 50,
+            bonus: 25,
+            total: 75,
+            ..ModifiedExperience::default()
+        });
+
+        assert_eq!(75, experience_tracker.get());
+        assert_eq!(vec![75], experience_tracker.session_history().0);
+        assert_eq!(25, experience_tracker.modifier_history()[0].bonus);
+    }
+
+    #[test]
+    fn test_entries() {
+        let mut experience_tracker = ExperienceTracker::new();
+        experience_tracker.start_session(1000);
+
+        experience_tracker.add_at(100, Some("quest:dragon"), 1010);
+        experience_tracker.remove_at(150, Some("death"), 1020);
+
+        let entries: Vec<_> = experience_tracker.entries().collect();
+        assert_eq!(2, entries.len());
+
+        assert_eq!(ExperienceChange::Gain, entries[0].change);
+        assert_eq!(Some(String::from("quest:dragon")), entries[0].source);
+        assert_eq!(1, entries[0].sequence);
+        assert_eq!(1010, entries[0].timestamp);
+        assert_eq!(100, entries[0].total);
+
+        assert_eq!(ExperienceChange::Loss, entries[1].change);
+        assert_eq!(100, entries[1].amount);
+        assert_eq!(0, entries[1].total);
+    }
+
+    #[test]
+    fn test_source_totals() {
+        let mut experience_tracker = ExperienceTracker::new();
+
+        experience_tracker.add_from(10, "kill:goblin");
+        experience_tracker.add_from(15, "kill:goblin");
+        experience_tracker.add_from(500, "quest:dragon");
+        experience_tracker.add(5);
+
+        let goblins = experience_tracker.source_totals("kill:goblin").unwrap();
+        assert_eq!(25, goblins.gained);
+        assert_eq!(2, goblins.entries);
+        assert_eq!(2, experience_tracker.sources().len());
+        assert_eq!(530, experience_tracker.session_gained());
+    }
+
+    #[test]
+    fn test_experience_per_hour() {
+        let mut experience_tracker = ExperienceTracker::new();
+        experience_tracker.start_session(0);
+
+        experience_tracker.add_at(500, None, 600);
+        experience_tracker.add_at(500, None, 1200);
+
+        assert_eq!(0., experience_tracker.experience_per_hour(0));
+        assert_eq!(2000., experience_tracker.experience_per_hour(1800));
+
+        experience_tracker.end_session(3600);
+        assert_eq!(Some(3600), experience_tracker.session_end());
+        assert_eq!(1000., experience_tracker.experience_per_hour(7200));
+    }
+
+    #[test]
+    fn test_retention() {
+        let mut experience_tracker = ExperienceTracker::new().with_retention(2);
+
+        for _ in 0..5 {
+            experience_tracker.add_from(10, "kill:goblin");
+        }
+
+        let entries: Vec<_> = experience_tracker.entries().collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(5, entries[1].sequence);
+
+        assert_eq!(50, experience_tracker.session_gained());
+        assert_eq!(
+            5,
+            experience_tracker
+                .source_totals("kill:goblin")
+                .unwrap()
+                .entries
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_legacy_format() {
+        let json = r#"{"experience_points":150,"session_gains":[100,75],"session_losses":[25],"tracking":true}"#;
+        let tracker: ExperienceTracker = serde_json::from_str(json).unwrap();
+
+        assert_eq!(150, tracker.get());
+        assert_eq!((vec![100, 75], vec![25]), tracker.session_history());
+        assert_eq!(175, tracker.session_gained());
+        assert_eq!(25, tracker.session_lost());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut tracker = ExperienceTracker::new().with_retention(2);
+        tracker.add_at(100, Some("quest"), 10);
+        tracker.add_at(50, None, 20);
+        tracker.remove_at(30, Some("death"), 30);
+
+        let json = serde_json::to_string(&tracker).unwrap();
+        let restored: ExperienceTracker = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(120, restored.get());
+        assert_eq!(Some(2), restored.retention());
+        assert_eq!(tracker.session_history(), restored.session_history());
+        assert_eq!(tracker.sources(), restored.sources());
+        assert_eq!(150, restored.session_gained());
+    }
 }
@@ -1,7 +1,19 @@
+#[cfg(feature = "big_experience")]
+mod big_experience;
+mod experience_loss;
+mod experience_modifier;
 mod experience_system;
 mod experience_table;
 mod experience_tracker;
 
-pub use experience_system::ExperienceSystem;
-pub use experience_table::ExperienceLevelUpTable;
-pub use experience_tracker::ExperienceTracker;
+pub use experience_loss::{ExperienceLoss, LossPolicy};
+pub use experience_modifier::{
+    DiminishingReturns, ExperienceBonus, ExperienceModifiers, ModifiedExperience, RestedExperience,
+};
+pub use experience_system::{ExperienceGain, ExperienceSystem};
+pub use experience_table::{ExperienceLevelUpTable, LevelUpTableError};
+pub(crate) use experience_tracker::now;
+pub use experience_tracker::{ExperienceChange, ExperienceEntry, ExperienceTracker, SourceTotals};
+
+#[cfg(feature = "big_experience")]
+pub use big_experience::{BigExperience, BigExperienceLevelUpTable, BigExperienceSystem};
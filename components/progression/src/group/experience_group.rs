@@ -0,0 +1,376 @@
+use crate::experience::{ExperienceLevelUpTable, ExperienceSystem, ExperienceTracker};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// The amount of entries kept in the ledger unless configured otherwise
+pub const DEFAULT_LEDGER_RETENTION: usize = 1000;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMember {
+    /// Seconds since the unix epoch
+    pub joined_at: u64,
+    /// Experience contributed since joining
+    pub contributed: u64,
+    /// Experience contributed since the last weekly reset
+    pub contributed_this_week: u64,
+}
+
+/// A single entry in the contribution ledger
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupContribution {
+    pub member: String,
+    pub amount: u64,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+/// What happens to a member's contributions when they leave
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LeavePolicy {
+    /// The group keeps the experience the member contributed
+    KeepContributions,
+    /// The member's contributions are removed from the group and the ledger
+    RevokeContributions,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GroupError {
+    /// The member is not part of the group
+    NotAMember(String),
+    /// The member is already part of the group
+    AlreadyAMember(String),
+}
+
+impl Display for GroupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupError::NotAMember(member) => write!(f, "'{}' is not a member", member),
+            GroupError::AlreadyAMember(member) => write!(f, "'{}' is already a member", member),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+/// Shared progression for parties and guilds, levelled by member contributions
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperienceGroup {
+    name: String,
+    system: ExperienceSystem,
+    members: BTreeMap<String, GroupMember>,
+    ledger: Vec<GroupContribution>,
+    /// The maximum amount of entries kept in the ledger, None for unlimited
+    #[cfg_attr(feature = "serde", serde(default = "default_retention"))]
+    retention: Option<usize>,
+    /// The maximum experience a single member may contribute per week
+    weekly_cap: Option<u64>,
+    /// Weekly contributions of members who left this week, restored when they rejoin
+    #[cfg_attr(feature = "serde", serde(default))]
+    departed_this_week: BTreeMap<String, u64>,
+}
+
+fn default_retention() -> Option<usize> {
+    Some(DEFAULT_LEDGER_RETENTION)
+}
+
+impl ExperienceGroup {
+    pub fn new(name: &str, levels: ExperienceLevelUpTable, weekly_cap: Option<u64>) -> Self {
+        Self {
+            name: name.to_string(),
+            system: ExperienceSystem::new(ExperienceTracker::new(), levels),
+            members: BTreeMap::new(),
+            ledger: vec![],
+            retention: default_retention(),
+            weekly_cap,
+            departed_this_week: BTreeMap::new(),
+        }
+    }
+
+    /// Limits the ledger to the most recent `limit` entries
+    pub fn with_retention(mut self, limit: usize) -> Self {
+        self.set_retention(Some(limit));
+        self
+    }
+
+    pub fn retention(&self) -> Option<usize> {
+        self.retention
+    }
+
+    /// Sets the maximum amount of entries kept in the ledger, None for unlimited
+    pub fn set_retention(&mut self, retention: Option<usize>) {
+        self.retention = retention;
+        self.truncate();
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn system(&self) -> &ExperienceSystem {
+        &self.system
+    }
+
+    pub fn get_level(&self) -> u64 {
+        self.system.get_level()
+    }
+
+    pub fn member(&self, member: &str) -> Option<&GroupMember> {
+        self.members.get(member)
+    }
+
+    pub fn members(&self) -> &BTreeMap<String, GroupMember> {
+        &self.members
+    }
+
+    pub fn ledger(&self) -> &[GroupContribution] {
+        &self.ledger
+    }
+
+    pub fn join(&mut self, member: &str, timestamp: u64) -> Result<(), GroupError> {
+        if self.members.contains_key(member) {
+            return Err(GroupError::AlreadyAMember(member.to_string()));
+        }
+
+        self.members.insert(
+            member.to_string(),
+            GroupMember {
+                joined_at: timestamp,
+                contributed_this_week: self.departed_this_week.remove(member).unwrap_or(0),
+                ..GroupMember::default()
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes the member from the group, returns the member as it was before leaving
+    pub fn leave(&mut self, member: &str, policy: LeavePolicy) -> Result<GroupMember, GroupError> {
+        let removed = self
+            .members
+            .remove(member)
+            .ok_or_else(|| GroupError::NotAMember(member.to_string()))?;
+
+        if removed.contributed_this_week > 0 {
+            self.departed_this_week
+                .insert(member.to_string(), removed.contributed_this_week);
+        }
+
+        if policy == LeavePolicy::RevokeContributions {
+            self.system.remove_experience(removed.contributed);
+            self.ledger
+                .retain(|contribution| contribution.member != member);
+        }
+
+        Ok(removed)
+    }
+
+    /// Experience the member may still contribute this week
+    pub fn remaining_this_week(&self, member: &str) -> Option<u64> {
+        let contributed = self.members.get(member)?.contributed_this_week;
+
+        Some(match self.weekly_cap {
+            None => u64::MAX - contributed,
+            Some(cap) => cap.saturating_sub(contributed),
+        })
+    }
+
+    /// Contributes experience to the group up to the weekly cap, returns the amount accepted
+    pub fn contribute(
+        &mut self,
+        member: &str,
+        amount: u64,
+        timestamp: u64,
+    ) -> Result<u64, GroupError> {
+        let accepted = self
+            .remaining_this_week(member)
+            .ok_or_else(|| GroupError::NotAMember(member.to_string()))?
+            .min(amount);
+
+        if accepted == 0 {
+            return Ok(0);
+        }
+
+        let entry = self.members.get_mut(member).unwrap();
+        entry.contributed = entry.contributed.saturating_add(accepted);
+        entry.contributed_this_week += accepted;
+
+        self.system.add_experience(accepted);
+        self.ledger.push(GroupContribution {
+            member: member.to_string(),
+            amount: accepted,
+            timestamp,
+        });
+        self.truncate();
+
+        Ok(accepted)
+    }
+
+    /// Moves experience from a member's own experience system into the group. The member's
+    /// loss policy limits what can be donated, a donation never goes into debt, and only the
+    /// accepted amount is taken. Returns the amount accepted.
+    pub fn donate(
+        &mut self,
+        member: &str,
+        donor: &mut ExperienceSystem,
+        amount: u64,
+        timestamp: u64,
+    ) -> Result<u64, GroupError> {
+        let offered = amount.min(donor.removable_experience());
+        let accepted = self.contribute(member, offered, timestamp)?;
+        donor.lose_experience_from(accepted, &self.name);
+
+        Ok(accepted)
+    }
+
+    /// Starts a new week, clearing every member's weekly contributions
+    pub fn reset_week(&mut self) {
+        for member in self.members.values_mut() {
+            member.contributed_this_week = 0;
+        }
+        self.departed_this_week.clear();
+    }
+
+    /// Drops the oldest ledger entries beyond the retention limit
+    fn truncate(&mut self) {
+        if let Some(retention) = self.retention {
+            let excess = self.ledger.len().saturating_sub(retention);
+            self.ledger.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::{ExperienceLevelUpTable, ExperienceSystem, LossPolicy};
+    use crate::group::{ExperienceGroup, GroupError, LeavePolicy, DEFAULT_LEDGER_RETENTION};
+
+    fn new_group() -> ExperienceGroup {
+        let mut group = ExperienceGroup::new(
+            "Guild",
+            ExperienceLevelUpTable::new(vec![100, 250]),
+            Some(150),
+        );
+
+        group.join("alice", 0).unwrap();
+        group.join("bob", 0).unwrap();
+
+        group
+    }
+
+    #[test]
+    fn test_contribute() {
+        let mut group = new_group();
+
+        assert_eq!(Ok(100), group.contribute("alice", 100, 10));
+        assert_eq!(2, group.get_level());
+        assert_eq!(
+            Err(GroupError::NotAMember(String::from("carol"))),
+            group.contribute("carol", 100, 10)
+        );
+        assert_eq!(
+            Err(GroupError::AlreadyAMember(String::from("bob"))),
+            group.join("bob", 10)
+        );
+
+        assert_eq!(1, group.ledger().len());
+        assert_eq!(100, group.member("alice").unwrap().contributed);
+    }
+
+    #[test]
+    fn test_weekly_cap() {
+        let mut group = new_group();
+
+        assert_eq!(Ok(100), group.contribute("alice", 100, 10));
+        assert_eq!(Ok(50), group.contribute("alice", 100, 20));
+        assert_eq!(Ok(0), group.contribute("alice", 100, 30));
+        assert_eq!(Some(150), group.remaining_this_week("bob"));
+
+        group.reset_week();
+        assert_eq!(Ok(100), group.contribute("alice", 100, 40));
+        assert_eq!(250, group.member("alice").unwrap().contributed);
+    }
+
+    #[test]
+    fn test_donate() {
+        let mut group = new_group();
+        let mut donor = ExperienceSystem::simple(120, vec![100, 250]);
+
+        assert_eq!(Ok(120), group.donate("bob", &mut donor, 500, 10));
+        assert_eq!(0, donor.get_experience());
+        assert_eq!(120, group.system().get_experience());
+        assert_eq!(120, donor.tracker().source_totals("Guild").unwrap().lost);
+    }
+
+    #[test]
+    fn test_donate_respects_loss_policy() {
+        let mut group = new_group();
+        let mut donor = ExperienceSystem::simple(130, vec![100, 250]);
+        donor.set_loss_policy(LossPolicy::Debt);
+
+        assert_eq!(Ok(30), group.donate("bob", &mut donor, 500, 10));
+        assert_eq!(100, donor.get_experience());
+        assert_eq!(0, donor.get_debt());
+        assert_eq!(Ok(0), group.donate("bob", &mut donor, 500, 20));
+    }
+
+    #[test]
+    fn test_leave() {
+        let mut group = new_group();
+        group.contribute("alice", 100, 10).unwrap();
+        group.contribute("bob", 100, 10).unwrap();
+
+        let alice = group
+            .leave("alice", LeavePolicy::KeepContributions)
+            .unwrap();
+        assert_eq!(100, alice.contributed);
+        assert_eq!(200, group.system().get_experience());
+
+        group
+            .leave("bob", LeavePolicy::RevokeContributions)
+            .unwrap();
+        assert_eq!(100, group.system().get_experience());
+        assert_eq!(1, group.ledger().len());
+        assert!(group.members().is_empty());
+        assert!(group.leave("bob", LeavePolicy::KeepContributions).is_err());
+    }
+
+    #[test]
+    fn test_rejoin_keeps_weekly_cap() {
+        let mut group = new_group();
+        assert_eq!(Ok(150), group.contribute("alice", 200, 10));
+
+        group
+            .leave("alice", LeavePolicy::KeepContributions)
+            .unwrap();
+        group.join("alice", 20).unwrap();
+        assert_eq!(Some(0), group.remaining_this_week("alice"));
+        assert_eq!(Ok(0), group.contribute("alice", 100, 30));
+
+        group.reset_week();
+        group
+            .leave("alice", LeavePolicy::KeepContributions)
+            .unwrap();
+        group.join("alice", 40).unwrap();
+        assert_eq!(Some(150), group.remaining_this_week("alice"));
+    }
+
+    #[test]
+    fn test_ledger_retention() {
+        let mut group = new_group().with_retention(2);
+
+        for timestamp in 0..5 {
+            group.contribute("bob", 10, timestamp).unwrap();
+        }
+
+        assert_eq!(2, group.ledger().len());
+        assert_eq!(3, group.ledger()[0].timestamp);
+        assert_eq!(50, group.member("bob").unwrap().contributed);
+
+        group.set_retention(Some(1));
+        assert_eq!(4, group.ledger()[0].timestamp);
+        assert_eq!(Some(DEFAULT_LEDGER_RETENTION), new_group().retention());
+    }
+}
@@ -0,0 +1,6 @@
+mod experience_group;
+
+pub use experience_group::{
+    ExperienceGroup, GroupContribution, GroupError, GroupMember, LeavePolicy,
+    DEFAULT_LEDGER_RETENTION,
+};
@@ -1,7 +1,16 @@
+pub mod award;
 pub mod experience;
+pub mod group;
+pub mod prestige;
+pub mod quest;
+pub mod report;
+pub mod season;
+pub mod skill;
+pub mod talent;
+pub mod tier;
 // pub mod milestone;
 
-trait LevelUpTable {
+pub trait LevelUpTable {
     fn name(&self) -> String;
 
     fn to_level(&self, experience_points: u64) -> u64;
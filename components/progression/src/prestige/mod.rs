@@ -0,0 +1,3 @@
+mod prestige_system;
+
+pub use prestige_system::{PrestigeFormula, PrestigeReward, PrestigeStatistics, PrestigeSystem};
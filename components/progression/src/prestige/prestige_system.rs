@@ -0,0 +1,313 @@
+use crate::experience::ExperienceSystem;
+
+/// How prestige currency is derived from the experience earned during a run
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrestigeFormula {
+    /// One point of currency for every `divisor` experience earned
+    Linear { divisor: u64 },
+    /// The square root of (experience earned / `divisor`), rewards long runs less generously
+    SquareRoot { divisor: u64 },
+    /// The number of times experience earned can be divided by `base`, before dropping below 1
+    Logarithmic { base: u64 },
+}
+
+impl PrestigeFormula {
+    pub fn currency(&self, experience_points: u64) -> u64 {
+        match self {
+            PrestigeFormula::Linear { divisor } => experience_points / (*divisor).max(1),
+            PrestigeFormula::SquareRoot { divisor } => {
+                ((experience_points / (*divisor).max(1)) as f64).sqrt() as u64
+            }
+            PrestigeFormula::Logarithmic { base } => {
+                if *base < 2 || experience_points == 0 {
+                    return 0;
+                }
+
+                experience_points.ilog(*base) as u64
+            }
+        }
+    }
+}
+
+/// Statistics that are kept across every prestige
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestigeStatistics {
+    /// How many times the player has prestiged
+    pub prestiges: u64,
+    /// All experience ever earned, including the current run
+    pub lifetime_experience: u64,
+    /// All prestige currency ever earned
+    pub lifetime_currency: u64,
+    /// The highest level reached in any run
+    pub highest_level: u64,
+}
+
+/// The result of a successful prestige
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestigeReward {
+    /// Prestige currency earned by this reset
+    pub currency: u64,
+    /// Experience that was earned during the run that was reset
+    pub run_experience: u64,
+    /// The level the player was at before the reset
+    pub level: u64,
+}
+
+/// A prestige (rebirth, ascension) layer on top of an ExperienceSystem
+///
+/// Resetting the experience system converts the experience earned during the run into
+/// prestige currency, every point of currency held grants a permanent experience bonus.
+///
+/// Other resources that should reset alongside the experience are up to the caller,
+/// use the returned PrestigeReward to determine when to do so.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestigeSystem {
+    experience: ExperienceSystem,
+    formula: PrestigeFormula,
+    /// The level required before a prestige is allowed
+    required_level: u64,
+    /// Percentage bonus to experience gained per point of currency held
+    bonus_per_currency: u64,
+    currency: u64,
+    run_experience: u64,
+    statistics: PrestigeStatistics,
+}
+
+impl PrestigeSystem {
+    /// Experience the system already holds counts towards the current run
+    pub fn new(
+        experience: ExperienceSystem,
+        formula: PrestigeFormula,
+        required_level: u64,
+        bonus_per_currency: u64,
+    ) -> Self {
+        let level = experience.get_level();
+        let run_experience = experience.get_experience();
+
+        Self {
+            experience,
+            formula,
+            required_level,
+            bonus_per_currency,
+            currency: 0,
+            run_experience,
+            statistics: PrestigeStatistics {
+                highest_level: level,
+                lifetime_experience: run_experience,
+                ..PrestigeStatistics::default()
+            },
+        }
+    }
+
+    pub fn experience(&self) -> &ExperienceSystem {
+        &self.experience
+    }
+
+    /// Access to the modifiers and loss policy of the experience system,
+    /// experience added or removed through it does not count towards the run
+    pub fn experience_mut(&mut self) -> &mut ExperienceSystem {
+        &mut self.experience
+    }
+
+    pub fn get_level(&self) -> u64 {
+        self.experience.get_level()
+    }
+
+    pub fn get_currency(&self) -> u64 {
+        self.currency
+    }
+
+    pub fn get_run_experience(&self) -> u64 {
+        self.run_experience
+    }
+
+    pub fn statistics(&self) -> &PrestigeStatistics {
+        &self.statistics
+    }
+
+    /// The permanent experience multiplier as a percentage, starts at 100
+    pub fn multiplier(&self) -> u64 {
+        100u64.saturating_add(self.currency.saturating_mul(self.bonus_per_currency))
+    }
+
+    /// Adds experience with the prestige multiplier applied, before the experience modifiers,
+    /// returns the experience gained
+    pub fn add_experience(&mut self, amount: u64) -> u64 {
+        let boosted =
+            (amount as u128 * self.multiplier() as u128 / 100).min(u64::MAX as u128) as u64;
+        let gained = self.experience.gain_experience(boosted).experience.total;
+
+        self.run_experience = self.run_experience.saturating_add(gained);
+        self.statistics.lifetime_experience =
+            self.statistics.lifetime_experience.saturating_add(gained);
+        self.statistics.highest_level = self.statistics.highest_level.max(self.get_level());
+
+        gained
+    }
+
+    /// Removes experience according to the loss policy, the experience lost
+    /// no longer counts towards the prestige currency of this run
+    pub fn remove_experience(&mut self, amount: u64) {
+        let lost = self.experience.lose_experience(amount).lost;
+        self.run_experience = self.run_experience.saturating_sub(lost);
+    }
+
+    /// The currency a prestige would award right now
+    pub fn pending_currency(&self) -> u64 {
+        self.formula.currency(self.run_experience)
+    }
+
+    pub fn can_prestige(&self) -> bool {
+        self.get_level() >= self.required_level
+    }
+
+    /// Resets the experience system and awards prestige currency,
+    /// returns None if the reset condition has not been met.
+    pub fn prestige(&mut self) -> Option<PrestigeReward> {
+        if !self.can_prestige() {
+            return None;
+        }
+
+        let reward = PrestigeReward {
+            currency: self.pending_currency(),
+            run_experience: self.run_experience,
+            level: self.get_level(),
+        };
+
+        self.currency = self.currency.saturating_add(reward.currency);
+        self.statistics.prestiges += 1;
+        self.statistics.lifetime_currency = self
+            .statistics
+            .lifetime_currency
+            .saturating_add(reward.currency);
+
+        self.experience.reset();
+        self.run_experience = 0;
+
+        Some(reward)
+    }
+
+    /// Spends prestige currency, lowering the multiplier. Returns false if there is not enough.
+    pub fn spend_currency(&mut self, amount: u64) -> bool {
+        if amount > self.currency {
+            return false;
+        }
+
+        self.currency -= amount;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::{ExperienceSystem, LossPolicy};
+    use crate::prestige::{PrestigeFormula, PrestigeSystem};
+
+    fn new_prestige_system() -> PrestigeSystem {
+        PrestigeSystem::new(
+            ExperienceSystem::simple(0, vec![100, 250, 500]),
+            PrestigeFormula::Linear { divisor: 50 },
+            3,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_formula() {
+        assert_eq!(5, PrestigeFormula::Linear { divisor: 100 }.currency(500));
+        assert_eq!(
+            3,
+            PrestigeFormula::SquareRoot { divisor: 100 }.currency(900)
+        );
+        assert_eq!(3, PrestigeFormula::Logarithmic { base: 10 }.currency(5000));
+        assert_eq!(0, PrestigeFormula::Logarithmic { base: 10 }.currency(0));
+    }
+
+    #[test]
+    fn test_prestige_requires_level() {
+        let mut system = new_prestige_system();
+
+        system.add_experience(100);
+        assert!(!system.can_prestige());
+        assert!(system.prestige().is_none());
+
+        system.add_experience(150);
+        assert!(system.can_prestige());
+    }
+
+    #[test]
+    fn test_prestige_resets_and_rewards() {
+        let mut system = new_prestige_system();
+
+        system.add_experience(300);
+        let reward = system.prestige().unwrap();
+
+        assert_eq!(6, reward.currency);
+        assert_eq!(300, reward.run_experience);
+        assert_eq!(3, reward.level);
+
+        assert_eq!(0, system.experience().get_experience());
+        assert_eq!(1, system.get_level());
+        assert_eq!(6, system.get_currency());
+        assert_eq!(160, system.multiplier());
+    }
+
+    #[test]
+    fn test_remove_experience_lowers_reward() {
+        let mut system = new_prestige_system();
+
+        system.add_experience(600);
+        system.remove_experience(100);
+        assert_eq!(500, system.get_run_experience());
+        assert_eq!(10, system.pending_currency());
+
+        system.remove_experience(1000);
+        assert_eq!(0, system.get_run_experience());
+        assert_eq!(0, system.pending_currency());
+    }
+
+    #[test]
+    fn test_existing_experience_and_policies() {
+        let mut system = PrestigeSystem::new(
+            ExperienceSystem::simple(300, vec![100, 250, 500]),
+            PrestigeFormula::Linear { divisor: 50 },
+            3,
+            10,
+        );
+        assert_eq!(300, system.get_run_experience());
+        assert_eq!(6, system.pending_currency());
+        assert_eq!(300, system.statistics().lifetime_experience);
+
+        system
+            .experience_mut()
+            .set_loss_policy(LossPolicy::FloorAtMilestone);
+        system.remove_experience(100);
+        assert_eq!(250, system.experience().get_experience());
+        assert_eq!(250, system.get_run_experience());
+    }
+
+    #[test]
+    fn test_multiplier_and_statistics() {
+        let mut system = new_prestige_system();
+
+        system.add_experience(500);
+        system.prestige().unwrap();
+
+        assert_eq!(200, system.multiplier());
+        assert_eq!(200, system.add_experience(100));
+
+        let statistics = system.statistics();
+        assert_eq!(1, statistics.prestiges);
+        assert_eq!(700, statistics.lifetime_experience);
+        assert_eq!(10, statistics.lifetime_currency);
+        assert_eq!(4, statistics.highest_level);
+
+        assert!(system.spend_currency(5));
+        assert!(!system.spend_currency(6));
+        assert_eq!(150, system.multiplier());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::experience::now;
+use std::time::Duration;
+
+/// A source of the current time, in seconds since the unix epoch
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+/// The system's wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        now()
+    }
+}
+
+/// A clock that only moves when told to, for tests and simulations
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ManualClock {
+    now: u64,
+}
+
+impl ManualClock {
+    pub fn new(now: u64) -> Self {
+        Self { now }
+    }
+
+    pub fn set(&mut self, now: u64) {
+        self.now = now;
+    }
+
+    pub fn advance(&mut self, duration: Duration) {
+        self.now = self.now.saturating_add(duration.as_secs());
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> u64 {
+        self.now
+    }
+}
@@ -0,0 +1,5 @@
+mod clock;
+mod quest_log;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use quest_log::{Objective, QuestCompletion, QuestError, QuestLog, ResetSchedule, StreakBonus};
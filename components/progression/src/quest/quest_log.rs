@@ -0,0 +1,392 @@
+use crate::experience::{ExperienceGain, ExperienceSystem};
+use crate::quest::{Clock, SystemClock};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+const DAY: u64 = 60 * 60 * 24;
+const WEEK: u64 = DAY * 7;
+/// The first Monday after the unix epoch, 1970-01-05 00:00 UTC
+const MONDAY: u64 = DAY * 4;
+
+/// When an objective's progress resets
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResetSchedule {
+    /// The objective can only be completed once
+    Never,
+    /// Resets every day, `offset` seconds after midnight UTC
+    Daily { offset: u64 },
+    /// Resets every week, `offset` seconds after Monday midnight UTC
+    Weekly { offset: u64 },
+    /// Resets every `period` seconds, counting from `start`
+    Interval { start: u64, period: u64 },
+}
+
+impl ResetSchedule {
+    /// Start of the period `now` falls in, None if the schedule never resets
+    /// or the first period has not started yet
+    pub fn period_start(&self, now: u64) -> Option<u64> {
+        now.checked_sub(self.elapsed(now)?)
+    }
+
+    /// When the period `now` falls in ends, None if the schedule never resets
+    pub fn next_reset(&self, now: u64) -> Option<u64> {
+        let (_, period) = self.anchor()?;
+
+        Some(now.saturating_add(period - self.elapsed(now)?))
+    }
+
+    /// Seconds since the start of the period `now` falls in
+    fn elapsed(&self, now: u64) -> Option<u64> {
+        let (anchor, period) = self.anchor()?;
+
+        Some((now as i128 - anchor as i128).rem_euclid(period as i128) as u64)
+    }
+
+    fn anchor(&self) -> Option<(u64, u64)> {
+        match *self {
+            ResetSchedule::Never => None,
+            ResetSchedule::Daily { offset } => Some((offset % DAY, DAY)),
+            ResetSchedule::Weekly { offset } => Some((MONDAY + offset % WEEK, WEEK)),
+            ResetSchedule::Interval { period: 0, .. } => None,
+            ResetSchedule::Interval { start, period } => Some((start, period)),
+        }
+    }
+}
+
+/// A goal that grants experience once its progress is reached
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Objective {
+    pub id: String,
+    pub goal: u64,
+    /// Experience granted on completion, before streak bonuses
+    pub experience: u64,
+    pub schedule: ResetSchedule,
+    progress: u64,
+    completed: bool,
+    /// Start of the period the progress belongs to
+    period: Option<u64>,
+    /// Start of the period the objective was last completed in
+    last_completed: Option<u64>,
+    streak: u64,
+}
+
+impl Objective {
+    pub fn new(id: &str, goal: u64, experience: u64, schedule: ResetSchedule) -> Self {
+        Self {
+            id: id.to_string(),
+            goal,
+            experience,
+            schedule,
+            progress: 0,
+            completed: false,
+            period: None,
+            last_completed: None,
+            streak: 0,
+        }
+    }
+
+    pub fn progress(&self) -> u64 {
+        self.progress
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    /// Number of consecutive periods the objective was completed in
+    pub fn streak(&self) -> u64 {
+        self.streak
+    }
+
+    /// Resets the progress when a new period started, and the streak when a period was missed
+    fn refresh(&mut self, now: u64) {
+        let period = self.schedule.period_start(now);
+        if period == self.period {
+            return;
+        }
+
+        let previous = period.and_then(|start| self.schedule.period_start(start.checked_sub(1)?));
+        if self.last_completed != period && self.last_completed != previous {
+            self.streak = 0;
+        }
+
+        self.period = period;
+        self.progress = 0;
+        self.completed = false;
+    }
+}
+
+/// Bonus experience for completing an objective in consecutive periods
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreakBonus {
+    /// Bonus percentage for every consecutive completion after the first
+    pub percentage_per_streak: u64,
+    /// The highest bonus percentage granted
+    pub max_percentage: u64,
+}
+
+/// The result of completing an objective
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestCompletion {
+    pub objective: String,
+    pub streak: u64,
+    pub bonus_percentage: u64,
+    pub gain: ExperienceGain,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QuestError {
+    /// The objective does not exist
+    UnknownObjective(String),
+    /// An objective with the same id already exists
+    DuplicateObjective(String),
+}
+
+impl Display for QuestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuestError::UnknownObjective(id) => write!(f, "Unknown objective '{}'", id),
+            QuestError::DuplicateObjective(id) => {
+                write!(f, "Objective '{}' is defined more than once", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuestError {}
+
+/// Tracks objectives that reset on a schedule and grant experience on completion
+///
+/// The clock is not serialized, a deserialized log uses the clock's default.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestLog<C: Clock = SystemClock> {
+    objectives: BTreeMap<String, Objective>,
+    streak_bonus: Option<StreakBonus>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: C,
+}
+
+impl<C: Clock> QuestLog<C> {
+    pub fn new(clock: C) -> Self {
+        Self {
+            objectives: BTreeMap::new(),
+            streak_bonus: None,
+            clock,
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    pub fn set_streak_bonus(&mut self, streak_bonus: Option<StreakBonus>) {
+        self.streak_bonus = streak_bonus;
+    }
+
+    pub fn streak_bonus(&self) -> Option<&StreakBonus> {
+        self.streak_bonus.as_ref()
+    }
+
+    pub fn add(&mut self, objective: Objective) -> Result<(), QuestError> {
+        if self.objectives.contains_key(&objective.id) {
+            return Err(QuestError::DuplicateObjective(objective.id));
+        }
+
+        let mut objective = objective;
+        objective.refresh(self.clock.now());
+        self.objectives.insert(objective.id.clone(), objective);
+
+        Ok(())
+    }
+
+    pub fn objective(&self, id: &str) -> Option<&Objective> {
+        self.objectives.get(id)
+    }
+
+    pub fn objectives(&self) -> impl Iterator<Item = &Objective> {
+        self.objectives.values()
+    }
+
+    /// Resets every objective whose period has passed, call after loading a saved log
+    pub fn refresh(&mut self) {
+        let now = self.clock.now();
+
+        for objective in self.objectives.values_mut() {
+            objective.refresh(now);
+        }
+    }
+
+    /// When the objective resets next, None if it never resets
+    pub fn next_reset(&self, id: &str) -> Option<u64> {
+        self.objectives
+            .get(id)?
+            .schedule
+            .next_reset(self.clock.now())
+    }
+
+    /// Adds progress to an objective, granting its experience once the goal is reached.
+    /// Progress on an objective that was already completed this period is ignored.
+    pub fn progress(
+        &mut self,
+        id: &str,
+        amount: u64,
+        system: &mut ExperienceSystem,
+    ) -> Result<Option<QuestCompletion>, QuestError> {
+        let now = self.clock.now();
+        let objective = self
+            .objectives
+            .get_mut(id)
+            .ok_or_else(|| QuestError::UnknownObjective(id.to_string()))?;
+
+        objective.refresh(now);
+        if objective.completed {
+            return Ok(None);
+        }
+
+        objective.progress = objective
+            .progress
+            .saturating_add(amount)
+            .min(objective.goal);
+        if objective.progress < objective.goal {
+            return Ok(None);
+        }
+
+        objective.completed = true;
+        objective.streak += 1;
+        objective.last_completed = objective.period;
+
+        let bonus_percentage = self.streak_bonus.map_or(0, |bonus| {
+            (objective.streak - 1)
+                .saturating_mul(bonus.percentage_per_streak)
+                .min(bonus.max_percentage)
+        });
+        let experience = (objective.experience as u128 * (100 + bonus_percentage as u128) / 100)
+            .min(u64::MAX as u128) as u64;
+
+        Ok(Some(QuestCompletion {
+            objective: objective.id.clone(),
+            streak: objective.streak,
+            bonus_percentage,
+            gain: system.gain_experience_at(experience, Some(objective.id.as_str()), now),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::ExperienceSystem;
+    use crate::quest::{ManualClock, Objective, QuestError, QuestLog, ResetSchedule, StreakBonus};
+    use std::time::Duration;
+
+    const DAY: u64 = 60 * 60 * 24;
+
+    fn new_log() -> QuestLog<ManualClock> {
+        let mut log = QuestLog::new(ManualClock::new(DAY * 100));
+        log.add(Objective::new(
+            "slay",
+            10,
+            100,
+            ResetSchedule::Daily { offset: 0 },
+        ))
+        .unwrap();
+
+        log
+    }
+
+    #[test]
+    fn test_schedules() {
+        assert_eq!(None, ResetSchedule::Never.next_reset(1000));
+        assert_eq!(
+            Some(DAY),
+            ResetSchedule::Daily { offset: 0 }.period_start(DAY + 50)
+        );
+        assert_eq!(
+            Some(DAY + 3600),
+            ResetSchedule::Daily { offset: 3600 }.next_reset(DAY)
+        );
+
+        // 1970-01-01 was a Thursday, the next Monday is four days later
+        assert_eq!(
+            Some(DAY * 4),
+            ResetSchedule::Weekly { offset: 0 }.next_reset(0)
+        );
+        assert_eq!(
+            Some(250),
+            ResetSchedule::Interval {
+                start: 50,
+                period: 100
+            }
+            .period_start(260)
+        );
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut log = new_log();
+        let mut system = ExperienceSystem::simple(0, vec![100, 200]);
+
+        assert_eq!(Ok(None), log.progress("slay", 6, &mut system));
+        assert_eq!(6, log.objective("slay").unwrap().progress());
+
+        let completion = log.progress("slay", 6, &mut system).unwrap().unwrap();
+        assert_eq!(100, completion.gain.experience.total);
+        assert_eq!(2, system.get_level());
+
+        assert_eq!(Ok(None), log.progress("slay", 10, &mut system));
+        assert_eq!(100, system.get_experience());
+        assert_eq!(
+            Err(QuestError::UnknownObjective(String::from("fetch"))),
+            log.progress("fetch", 1, &mut system)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut log = new_log();
+        let mut system = ExperienceSystem::simple(0, vec![100, 200]);
+
+        log.progress("slay", 10, &mut system).unwrap();
+        assert_eq!(Some(DAY * 101), log.next_reset("slay"));
+
+        log.clock_mut().advance(Duration::from_secs(DAY));
+        log.refresh();
+        assert!(!log.objective("slay").unwrap().is_completed());
+        assert_eq!(0, log.objective("slay").unwrap().progress());
+        assert!(log.progress("slay", 10, &mut system).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut log = new_log();
+        let mut system = ExperienceSystem::simple(0, vec![]);
+        log.set_streak_bonus(Some(StreakBonus {
+            percentage_per_streak: 10,
+            max_percentage: 15,
+        }));
+
+        for (streak, experience) in [(1, 100), (2, 110), (3, 115)] {
+            let completion = log.progress("slay", 10, &mut system).unwrap().unwrap();
+            assert_eq!(streak, completion.streak);
+            assert_eq!(experience, completion.gain.experience.total);
+
+            log.clock_mut().advance(Duration::from_secs(DAY));
+        }
+
+        log.clock_mut().advance(Duration::from_secs(DAY));
+        log.refresh();
+        assert_eq!(0, log.objective("slay").unwrap().streak());
+
+        let completion = log.progress("slay", 10, &mut system).unwrap().unwrap();
+        assert_eq!(1, completion.streak);
+        assert_eq!(0, completion.bonus_percentage);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::experience::ExperienceLevelUpTable;
+use crate::LevelUpTable;
+use std::fmt::Write;
+
+/// A single level of an experience curve
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelRow {
+    pub level: u64,
+    /// Total experience required to reach the level
+    pub experience: u64,
+    /// Experience required since the previous level
+    pub delta: u64,
+    /// Hours spent reaching the level from the previous one, given the report's rate
+    pub hours: Option<f64>,
+    /// Hours spent reaching the level from level 1, given the report's rate
+    pub total_hours: Option<f64>,
+}
+
+/// A per-level overview of an ExperienceLevelUpTable, so curves can be reviewed outside the game
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveReport {
+    name: String,
+    experience_per_hour: Option<f64>,
+    rows: Vec<LevelRow>,
+}
+
+impl CurveReport {
+    /// Creates a report, time-to-level is only estimated when an experience per hour rate is given
+    pub fn new(table: &ExperienceLevelUpTable, experience_per_hour: Option<f64>) -> Self {
+        let rate = experience_per_hour.filter(|rate| *rate > 0.);
+        let mut rows = vec![LevelRow {
+            level: 1,
+            experience: 0,
+            delta: 0,
+            hours: rate.map(|_| 0.),
+            total_hours: rate.map(|_| 0.),
+        }];
+
+        let mut previous = 0;
+        for (index, milestone) in table.milestones().iter().enumerate() {
+            let delta = milestone - previous;
+            previous = *milestone;
+
+            rows.push(LevelRow {
+                level: index as u64 + 2,
+                experience: *milestone,
+                delta,
+                hours: rate.map(|rate| delta as f64 / rate),
+                total_hours: rate.map(|rate| *milestone as f64 / rate),
+            });
+        }
+
+        Self {
+            name: table.name(),
+            experience_per_hour: rate,
+            rows,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn experience_per_hour(&self) -> Option<f64> {
+        self.experience_per_hour
+    }
+
+    pub fn rows(&self) -> &[LevelRow] {
+        &self.rows
+    }
+
+    /// Comma separated values with a header row, hours are left out without a rate
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("level,experience,delta");
+        if self.experience_per_hour.is_some() {
+            csv.push_str(",hours,total_hours");
+        }
+        csv.push('\n');
+
+        for row in &self.rows {
+            let _ = write!(csv, "{},{},{}", row.level, row.experience, row.delta);
+            if let (Some(hours), Some(total)) = (row.hours, row.total_hours) {
+                let _ = write!(csv, ",{:.2},{:.2}", hours, total);
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// A Markdown table, titled with the name of the level up table
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("## {}\n\n", self.name);
+
+        if self.experience_per_hour.is_some() {
+            markdown.push_str("| Level | Experience | Delta | Hours | Total hours |\n");
+            markdown.push_str("|------:|-----------:|------:|------:|------------:|\n");
+        } else {
+            markdown.push_str("| Level | Experience | Delta |\n");
+            markdown.push_str("|------:|-----------:|------:|\n");
+        }
+
+        for row in &self.rows {
+            let _ = write!(
+                markdown,
+                "| {} | {} | {} |",
+                row.level, row.experience, row.delta
+            );
+            if let (Some(hours), Some(total)) = (row.hours, row.total_hours) {
+                let _ = write!(markdown, " {:.2} | {:.2} |", hours, total);
+            }
+            markdown.push('\n');
+        }
+
+        markdown
+    }
+
+    /// A horizontal bar chart of the experience required per level,
+    /// the longest bar is `width` characters wide
+    pub fn ascii_chart(&self, width: usize) -> String {
+        let max = self.rows.iter().map(|row| row.delta).max().unwrap_or(0);
+        let level_width = self
+            .rows
+            .last()
+            .map_or(1, |row| row.level.to_string().len());
+        let mut chart = String::new();
+
+        for row in self.rows.iter().skip(1) {
+            let length = match max {
+                0 => 0,
+                max => (row.delta as u128 * width as u128 / max as u128) as usize,
+            };
+
+            let _ = writeln!(
+                chart,
+                "{:>level_width$} | {:<width$} {}",
+                row.level,
+                "#".repeat(length),
+                row.delta,
+            );
+        }
+
+        chart
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::ExperienceLevelUpTable;
+    use crate::report::CurveReport;
+
+    fn new_table() -> ExperienceLevelUpTable {
+        ExperienceLevelUpTable::named(String::from("Test"), vec![100, 300, 700])
+    }
+
+    #[test]
+    fn test_rows() {
+        let report = CurveReport::new(&new_table(), Some(200.));
+
+        assert_eq!(4, report.rows().len());
+        assert_eq!(400, report.rows()[3].delta);
+        assert_eq!(Some(2.), report.rows()[3].hours);
+        assert_eq!(Some(3.5), report.rows()[3].total_hours);
+
+        let report = CurveReport::new(&new_table(), Some(0.));
+        assert_eq!(None, report.experience_per_hour());
+        assert_eq!(None, report.rows()[1].hours);
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "level,experience,delta\n1,0,0\n2,100,100\n3,300,200\n4,700,400\n",
+            CurveReport::new(&new_table(), None).to_csv()
+        );
+
+        let csv = CurveReport::new(&new_table(), Some(100.)).to_csv();
+        assert_eq!(
+            Some("level,experience,delta,hours,total_hours"),
+            csv.lines().next()
+        );
+        assert_eq!(Some("4,700,400,4.00,7.00"), csv.lines().last());
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = CurveReport::new(&new_table(), None).to_markdown();
+
+        assert!(markdown.starts_with("## Test\n"));
+        assert!(markdown.contains("| 3 | 300 | 200 |\n"));
+    }
+
+    #[test]
+    fn test_ascii_chart() {
+        let chart = CurveReport::new(&new_table(), None).ascii_chart(8);
+
+        assert_eq!(
+            vec!["2 | ##       100", "3 | ####     200", "4 | ######## 400"],
+            chart.lines().collect::<Vec<&str>>()
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod curve_report;
+
+pub use curve_report::{CurveReport, LevelRow};
@@ -0,0 +1,5 @@
+mod season_error;
+mod season_pass;
+
+pub use season_error::SeasonError;
+pub use season_pass::{CatchUpBonus, RewardTrack, SeasonPass, SeasonReward};
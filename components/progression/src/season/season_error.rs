@@ -0,0 +1,45 @@
+use crate::season::RewardTrack;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SeasonError {
+    /// The season has not started yet
+    NotStarted { starts_at: u64 },
+    /// The season is over
+    Ended { ended_at: u64 },
+    /// There is no reward on this track at this tier
+    NoReward { track: RewardTrack, tier: u64 },
+    /// The tier has not been reached yet
+    TierNotReached { tier: u64, current: u64 },
+    /// The premium track has not been unlocked
+    PremiumRequired,
+    /// The reward was claimed before
+    AlreadyClaimed { track: RewardTrack, tier: u64 },
+}
+
+impl Display for SeasonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeasonError::NotStarted { starts_at } => {
+                write!(f, "Season has not started, it starts at {}", starts_at)
+            }
+            SeasonError::Ended { ended_at } => write!(f, "Season ended at {}", ended_at),
+            SeasonError::NoReward { track, tier } => {
+                write!(f, "No {:?} reward at tier {}", track, tier)
+            }
+            SeasonError::TierNotReached { tier, current } => {
+                write!(
+                    f,
+                    "Tier {} has not been reached, currently at {}",
+                    tier, current
+                )
+            }
+            SeasonError::PremiumRequired => write!(f, "The premium track is locked"),
+            SeasonError::AlreadyClaimed { track, tier } => {
+                write!(f, "{:?} reward at tier {} was already claimed", track, tier)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeasonError {}
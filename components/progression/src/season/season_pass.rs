@@ -0,0 +1,360 @@
+use crate::experience::{ExperienceLevelUpTable, ExperienceTracker, ModifiedExperience};
+use crate::season::SeasonError;
+use crate::LevelUpTable;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RewardTrack {
+    /// Rewards available to every player
+    Free,
+    /// Rewards that require the premium track to be unlocked
+    Premium,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonReward {
+    pub track: RewardTrack,
+    pub tier: u64,
+    /// Identifier of the reward, e.g. an item or cosmetic id
+    pub reward: String,
+    pub claimed: bool,
+}
+
+/// Extra experience for players behind the pace needed to finish the season
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatchUpBonus {
+    /// Bonus percentage for every tier behind the expected tier
+    pub percentage_per_tier: u64,
+    /// The highest bonus percentage granted
+    pub max_percentage: u64,
+}
+
+/// A season-long progression track with free and premium rewards
+///
+/// Every player starts at tier 1, each milestone of the table unlocks the next tier.
+/// Timestamps are seconds since the unix epoch.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonPass {
+    name: String,
+    starts_at: u64,
+    ends_at: u64,
+    tiers: ExperienceLevelUpTable,
+    tracker: ExperienceTracker,
+    rewards: Vec<SeasonReward>,
+    premium: bool,
+    catch_up: Option<CatchUpBonus>,
+}
+
+impl SeasonPass {
+    pub fn new(name: &str, starts_at: u64, ends_at: u64, tiers: ExperienceLevelUpTable) -> Self {
+        Self {
+            name: name.to_string(),
+            starts_at,
+            ends_at,
+            tiers,
+            tracker: ExperienceTracker::new(),
+            rewards: vec![],
+            premium: false,
+            catch_up: None,
+        }
+    }
+
+    /// A season of `tiers` tiers that each take the same amount of experience
+    pub fn uniform(
+        name: &str,
+        starts_at: u64,
+        ends_at: u64,
+        tiers: u64,
+        experience_per_tier: u64,
+    ) -> Self {
+        let milestones = (1..tiers)
+            .map(|tier| tier.saturating_mul(experience_per_tier))
+            .collect();
+
+        Self::new(
+            name,
+            starts_at,
+            ends_at,
+            ExperienceLevelUpTable::named(name.to_string(), milestones),
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn starts_at(&self) -> u64 {
+        self.starts_at
+    }
+
+    pub fn ends_at(&self) -> u64 {
+        self.ends_at
+    }
+
+    pub fn is_active(&self, now: u64) -> bool {
+        now >= self.starts_at && now < self.ends_at
+    }
+
+    pub fn tiers(&self) -> &ExperienceLevelUpTable {
+        &self.tiers
+    }
+
+    pub fn tracker(&self) -> &ExperienceTracker {
+        &self.tracker
+    }
+
+    pub fn get_tier(&self) -> u64 {
+        self.tiers.to_level(self.tracker.get())
+    }
+
+    pub fn max_tier(&self) -> u64 {
+        self.tiers.max_level()
+    }
+
+    pub fn get_experience(&self) -> u64 {
+        self.tracker.get()
+    }
+
+    pub fn get_experience_remaining(&self) -> Option<u64> {
+        self.tiers
+            .get_next_milestone(self.tracker.get())
+            .map(|milestone| milestone - self.tracker.get())
+    }
+
+    pub fn is_premium(&self) -> bool {
+        self.premium
+    }
+
+    pub fn unlock_premium(&mut self) {
+        self.premium = true;
+    }
+
+    pub fn set_catch_up(&mut self, catch_up: Option<CatchUpBonus>) {
+        self.catch_up = catch_up;
+    }
+
+    pub fn catch_up(&self) -> Option<&CatchUpBonus> {
+        self.catch_up.as_ref()
+    }
+
+    /// Adds a reward, replacing any reward on the same track and tier
+    pub fn add_reward(&mut self, track: RewardTrack, tier: u64, reward: &str) {
+        self.rewards.retain(|r| r.track != track || r.tier != tier);
+        self.rewards.push(SeasonReward {
+            track,
+            tier,
+            reward: reward.to_string(),
+            claimed: false,
+        });
+        self.rewards.sort_by_key(|r| (r.tier, r.track));
+    }
+
+    pub fn rewards(&self) -> &[SeasonReward] {
+        &self.rewards
+    }
+
+    /// The tier a player progressing evenly through the season would be at
+    pub fn expected_tier(&self, now: u64) -> u64 {
+        let duration = self.ends_at.saturating_sub(self.starts_at);
+        if duration == 0 {
+            return self.max_tier();
+        }
+
+        let elapsed = now.saturating_sub(self.starts_at).min(duration);
+
+        1 + ((self.max_tier() - 1) as u128 * elapsed as u128 / duration as u128) as u64
+    }
+
+    /// The catch-up bonus percentage at this point of the season
+    pub fn catch_up_percentage(&self, now: u64) -> u64 {
+        match self.catch_up {
+            None => 0,
+            Some(catch_up) => {
+                let behind = self.expected_tier(now).saturating_sub(self.get_tier());
+
+                behind
+                    .saturating_mul(catch_up.percentage_per_tier)
+                    .min(catch_up.max_percentage)
+            }
+        }
+    }
+
+    /// Adds experience while the season is active, including the catch-up bonus
+    pub fn add_experience(
+        &mut self,
+        amount: u64,
+        now: u64,
+    ) -> Result<ModifiedExperience, SeasonError> {
+        if now < self.starts_at {
+            return Err(SeasonError::NotStarted {
+                starts_at: self.starts_at,
+            });
+        }
+
+        if now >= self.ends_at {
+            return Err(SeasonError::Ended {
+                ended_at: self.ends_at,
+            });
+        }
+
+        let bonus = (amount as u128 * self.catch_up_percentage(now) as u128 / 100)
+            .min(u64::MAX as u128) as u64;
+        let experience = ModifiedExperience {
+            base: amount,
+            bonus,
+            total: amount.saturating_add(bonus),
+            ..ModifiedExperience::default()
+        };
+
+        self.tracker
+            .add_modified_at(experience.clone(), Some(self.name.as_str()), now);
+
+        Ok(experience)
+    }
+
+    /// Claims a reward, returns its identifier
+    pub fn claim(&mut self, track: RewardTrack, tier: u64) -> Result<String, SeasonError> {
+        let current = self.get_tier();
+        let premium = self.premium;
+
+        let reward = self
+            .rewards
+            .iter_mut()
+            .find(|r| r.track == track && r.tier == tier)
+            .ok_or(SeasonError::NoReward { track, tier })?;
+
+        if reward.claimed {
+            return Err(SeasonError::AlreadyClaimed { track, tier });
+        }
+
+        if track == RewardTrack::Premium && !premium {
+            return Err(SeasonError::PremiumRequired);
+        }
+
+        if tier > current {
+            return Err(SeasonError::TierNotReached { tier, current });
+        }
+
+        reward.claimed = true;
+        Ok(reward.reward.clone())
+    }
+
+    /// Every reward that can be claimed right now
+    pub fn claimable(&self) -> Vec<&SeasonReward> {
+        let current = self.get_tier();
+
+        self.rewards
+            .iter()
+            .filter(|r| !r.claimed && r.tier <= current)
+            .filter(|r| r.track == RewardTrack::Free || self.premium)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::season::{CatchUpBonus, RewardTrack, SeasonError, SeasonPass};
+
+    const DAY: u64 = 60 * 60 * 24;
+
+    fn new_season() -> SeasonPass {
+        let mut season = SeasonPass::uniform("Season 1", 0, 10 * DAY, 11, 1000);
+        season.add_reward(RewardTrack::Free, 2, "banner");
+        season.add_reward(RewardTrack::Premium, 2, "skin");
+        season.add_reward(RewardTrack::Free, 11, "mount");
+
+        season
+    }
+
+    #[test]
+    fn test_tiers() {
+        let mut season = new_season();
+
+        assert_eq!(1, season.get_tier());
+        assert_eq!(11, season.max_tier());
+
+        season.add_experience(2500, DAY).unwrap();
+        assert_eq!(3, season.get_tier());
+        assert_eq!(Some(500), season.get_experience_remaining());
+        assert_eq!(1, season.tracker().entries().count());
+    }
+
+    #[test]
+    fn test_season_dates() {
+        let mut season = SeasonPass::uniform("Season 2", DAY, 2 * DAY, 5, 100);
+
+        assert!(!season.is_active(0));
+        assert_eq!(
+            Err(SeasonError::NotStarted { starts_at: DAY }),
+            season.add_experience(100, 0)
+        );
+        assert!(season.add_experience(100, DAY).is_ok());
+        assert_eq!(
+            Err(SeasonError::Ended { ended_at: 2 * DAY }),
+            season.add_experience(100, 2 * DAY)
+        );
+    }
+
+    #[test]
+    fn test_claim() {
+        let mut season = new_season();
+
+        assert_eq!(
+            Err(SeasonError::TierNotReached {
+                tier: 2,
+                current: 1
+            }),
+            season.claim(RewardTrack::Free, 2)
+        );
+
+        season.add_experience(1000, 0).unwrap();
+        assert_eq!(1, season.claimable().len());
+        assert_eq!(
+            Ok(String::from("banner")),
+            season.claim(RewardTrack::Free, 2)
+        );
+        assert_eq!(
+            Err(SeasonError::AlreadyClaimed {
+                track: RewardTrack::Free,
+                tier: 2
+            }),
+            season.claim(RewardTrack::Free, 2)
+        );
+
+        assert_eq!(
+            Err(SeasonError::PremiumRequired),
+            season.claim(RewardTrack::Premium, 2)
+        );
+        season.unlock_premium();
+        assert_eq!(
+            Ok(String::from("skin")),
+            season.claim(RewardTrack::Premium, 2)
+        );
+        assert!(season.claimable().is_empty());
+    }
+
+    #[test]
+    fn test_catch_up() {
+        let mut season = new_season();
+        season.set_catch_up(Some(CatchUpBonus {
+            percentage_per_tier: 20,
+            max_percentage: 100,
+        }));
+
+        assert_eq!(0, season.catch_up_percentage(0));
+        assert_eq!(6, season.expected_tier(5 * DAY));
+        assert_eq!(100, season.catch_up_percentage(5 * DAY));
+
+        let experience = season.add_experience(1000, 5 * DAY).unwrap();
+        assert_eq!(1000, experience.bonus);
+        assert_eq!(3, season.get_tier());
+        assert_eq!(60, season.catch_up_percentage(5 * DAY));
+
+        let experience = season.add_experience(1000, 5 * DAY).unwrap();
+        assert_eq!(600, experience.bonus);
+        assert_eq!(1600, experience.total);
+    }
+}
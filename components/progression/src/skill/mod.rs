@@ -0,0 +1,3 @@
+mod skill_book;
+
+pub use skill_book::{SkillAction, SkillBook};
@@ -0,0 +1,229 @@
+use crate::experience::{
+    ExperienceGain, ExperienceLevelUpTable, ExperienceSystem, ExperienceTracker,
+};
+use std::collections::BTreeMap;
+
+/// An action that trains a skill whenever it is performed, e.g. "mine_copper" trains "Mining"
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkillAction {
+    pub skill: String,
+    pub experience: u64,
+}
+
+/// A collection of independently levelled skills (Mining, Smithing, Archery, ..)
+///
+/// Every skill levels with its own copy of a level up table, the default table unless
+/// it was added with its own. Saves therefore hold one table per skill.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkillBook {
+    /// Copied into every skill added with add_skill, skills added before a change keep theirs
+    #[cfg_attr(feature = "serde", serde(alias = "table"))]
+    default_table: ExperienceLevelUpTable,
+    skills: BTreeMap<String, ExperienceSystem>,
+    actions: BTreeMap<String, SkillAction>,
+    /// Percentage each skill's level contributes towards the combat level
+    combat_weights: BTreeMap<String, u64>,
+}
+
+impl SkillBook {
+    pub fn new(default_table: ExperienceLevelUpTable) -> Self {
+        Self {
+            default_table,
+            skills: BTreeMap::new(),
+            actions: BTreeMap::new(),
+            combat_weights: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a skill with a copy of the default level up table, returns false if it already exists
+    pub fn add_skill(&mut self, name: &str) -> bool {
+        self.add_skill_with_table(name, self.default_table.clone())
+    }
+
+    pub fn default_table(&self) -> &ExperienceLevelUpTable {
+        &self.default_table
+    }
+
+    /// Replaces the table copied into skills added from now on, existing skills keep their copy
+    pub fn set_default_table(&mut self, table: ExperienceLevelUpTable) {
+        self.default_table = table;
+    }
+
+    /// Adds a skill with its own level up table, returns false if it already exists
+    pub fn add_skill_with_table(&mut self, name: &str, table: ExperienceLevelUpTable) -> bool {
+        if self.skills.contains_key(name) {
+            return false;
+        }
+
+        self.skills.insert(
+            name.to_string(),
+            ExperienceSystem::new(ExperienceTracker::new(), table),
+        );
+        true
+    }
+
+    pub fn skill(&self, name: &str) -> Option<&ExperienceSystem> {
+        self.skills.get(name)
+    }
+
+    pub fn skill_mut(&mut self, name: &str) -> Option<&mut ExperienceSystem> {
+        self.skills.get_mut(name)
+    }
+
+    pub fn skills(&self) -> impl Iterator<Item = (&String, &ExperienceSystem)> {
+        self.skills.iter()
+    }
+
+    /// The level of a skill, None if the skill does not exist
+    pub fn level(&self, name: &str) -> Option<u64> {
+        self.skills.get(name).map(|skill| skill.get_level())
+    }
+
+    /// Adds experience to a skill, None if the skill does not exist
+    pub fn train(&mut self, name: &str, experience: u64) -> Option<ExperienceGain> {
+        self.skills
+            .get_mut(name)
+            .map(|skill| skill.gain_experience(experience))
+    }
+
+    /// Registers an action that trains `skill` by `experience` whenever it is performed
+    pub fn register_action(&mut self, action: &str, skill: &str, experience: u64) {
+        self.actions.insert(
+            action.to_string(),
+            SkillAction {
+                skill: skill.to_string(),
+                experience,
+            },
+        );
+    }
+
+    /// Trains the skill tied to the action, the action is used as source in the skill's history.
+    /// None if the action or its skill does not exist.
+    pub fn perform(&mut self, action: &str) -> Option<ExperienceGain> {
+        let SkillAction { skill, experience } = self.actions.get(action)?;
+
+        self.skills
+            .get_mut(skill)
+            .map(|skill| skill.gain_experience_from(*experience, action))
+    }
+
+    /// The sum of every skill level, saturating at u64::MAX
+    pub fn total_level(&self) -> u64 {
+        self.skills
+            .values()
+            .fold(0u64, |total, skill| total.saturating_add(skill.get_level()))
+    }
+
+    /// Sets the percentage the skill's level contributes towards the combat level
+    pub fn set_combat_weight(&mut self, skill: &str, percentage: u64) {
+        self.combat_weights.insert(skill.to_string(), percentage);
+    }
+
+    /// The weighted sum of the combat skill levels, at least 1
+    pub fn combat_level(&self) -> u64 {
+        let weighted = self
+            .combat_weights
+            .iter()
+            .filter_map(|(name, weight)| {
+                self.level(name).map(|level| level.saturating_mul(*weight))
+            })
+            .fold(0u64, u64::saturating_add);
+
+        (weighted / 100).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::ExperienceLevelUpTable;
+    use crate::skill::SkillBook;
+
+    fn new_skill_book() -> SkillBook {
+        let mut book = SkillBook::new(ExperienceLevelUpTable::new(vec![100, 250, 500]));
+
+        book.add_skill("Mining");
+        book.add_skill("Smithing");
+        book.add_skill_with_table("Archery", ExperienceLevelUpTable::new(vec![10, 20]));
+
+        book
+    }
+
+    #[test]
+    fn test_skills() {
+        let mut book = new_skill_book();
+
+        assert!(!book.add_skill("Mining"));
+        assert_eq!(3, book.skills().count());
+        assert_eq!(3, book.total_level());
+
+        assert_eq!(2, book.train("Mining", 100).unwrap().level);
+        assert_eq!(3, book.train("Archery", 20).unwrap().level);
+        assert!(book.train("Fishing", 100).is_none());
+
+        assert_eq!(Some(2), book.level("Mining"));
+        assert_eq!(Some(1), book.level("Smithing"));
+        assert_eq!(6, book.total_level());
+    }
+
+    #[test]
+    fn test_train_on_use() {
+        let mut book = new_skill_book();
+        book.register_action("mine_copper", "Mining", 50);
+        book.register_action("mine_nothing", "Digging", 50);
+
+        book.perform("mine_copper").unwrap();
+        let gain = book.perform("mine_copper").unwrap();
+        assert_eq!(1, gain.levels_gained());
+
+        assert!(book.perform("mine_nothing").is_none());
+        assert!(book.perform("fish_trout").is_none());
+
+        let totals = book
+            .skill("Mining")
+            .unwrap()
+            .tracker()
+            .source_totals("mine_copper")
+            .unwrap();
+        assert_eq!(100, totals.gained);
+    }
+
+    #[test]
+    fn test_combat_level() {
+        let mut book = new_skill_book();
+
+        assert_eq!(1, book.combat_level());
+
+        book.set_combat_weight("Archery", 100);
+        book.set_combat_weight("Mining", 50);
+        book.train("Archery", 20);
+        book.train("Mining", 500);
+
+        assert_eq!(5, book.combat_level());
+
+        book.set_combat_weight("Smithing", u64::MAX);
+        assert_eq!(u64::MAX / 100, book.combat_level());
+    }
+
+    #[test]
+    fn test_default_table() {
+        let mut book = new_skill_book();
+        book.set_default_table(ExperienceLevelUpTable::new(vec![1]));
+        book.add_skill("Fishing");
+
+        assert_eq!(Some(2), book.train("Fishing", 1).map(|gain| gain.level));
+        assert_eq!(Some(1), book.train("Mining", 1).map(|gain| gain.level));
+        assert_eq!(&[1], book.default_table().milestones());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_table_key() {
+        let json = serde_json::to_string(&new_skill_book()).unwrap();
+        let json = json.replace("\"default_table\"", "\"table\"");
+
+        let book: SkillBook = serde_json::from_str(&json).unwrap();
+        assert_eq!(&[100, 250, 500], book.default_table().milestones());
+    }
+}
@@ -0,0 +1,7 @@
+mod talent_allocation;
+mod talent_error;
+mod talent_tree;
+
+pub use talent_allocation::{RespecCost, TalentAllocation};
+pub use talent_error::TalentError;
+pub use talent_tree::{TalentNode, TalentPrerequisite, TalentTree};
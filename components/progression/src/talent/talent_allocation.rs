@@ -0,0 +1,292 @@
+use crate::experience::ExperienceSystem;
+use crate::talent::{TalentError, TalentTree};
+use std::collections::BTreeMap;
+
+/// What it costs to reset every allocated talent point
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RespecCost {
+    Free,
+    Flat(u64),
+    /// Starts at `base` and increases by `increase` for every previous respec, up to `max`
+    Escalating {
+        base: u64,
+        increase: u64,
+        max: u64,
+    },
+}
+
+impl RespecCost {
+    /// The cost of the next respec, after `respecs` have already been done
+    pub fn cost(&self, respecs: u64) -> u64 {
+        match self {
+            RespecCost::Free => 0,
+            RespecCost::Flat(cost) => *cost,
+            RespecCost::Escalating {
+                base,
+                increase,
+                max,
+            } => base
+                .saturating_add(increase.saturating_mul(respecs))
+                .min(*max),
+        }
+    }
+}
+
+/// The talent points a character has spent in a single talent tree
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalentAllocation {
+    points: u64,
+    ranks: BTreeMap<String, u64>,
+    respecs: u64,
+}
+
+impl TalentAllocation {
+    pub fn new(points: u64) -> Self {
+        Self {
+            points,
+            ..Self::default()
+        }
+    }
+
+    /// An allocation with the points granted by the tree at the system's level
+    pub fn for_level(tree: &TalentTree, system: &ExperienceSystem) -> Self {
+        Self::new(tree.points_for_level(system.get_level()))
+    }
+
+    /// Updates the points granted to those of the tree at the system's level
+    pub fn update_points(&mut self, tree: &TalentTree, system: &ExperienceSystem) {
+        self.points = tree.points_for_level(system.get_level());
+    }
+
+    pub fn points(&self) -> u64 {
+        self.points
+    }
+
+    pub fn spent(&self) -> u64 {
+        self.ranks.values().sum()
+    }
+
+    /// Unspent points, zero if more points have been spent than granted
+    pub fn available(&self) -> u64 {
+        self.points.saturating_sub(self.spent())
+    }
+
+    pub fn rank(&self, talent: &str) -> u64 {
+        self.ranks.get(talent).copied().unwrap_or(0)
+    }
+
+    pub fn ranks(&self) -> &BTreeMap<String, u64> {
+        &self.ranks
+    }
+
+    pub fn respecs(&self) -> u64 {
+        self.respecs
+    }
+
+    /// Checks whether one more rank can be put into the talent
+    pub fn can_allocate(&self, tree: &TalentTree, talent: &str) -> Result<(), TalentError> {
+        let node = tree
+            .node(talent)
+            .ok_or_else(|| TalentError::UnknownTalent(talent.to_string()))?;
+
+        if self.available() == 0 {
+            return Err(TalentError::NoPointsAvailable);
+        }
+
+        if self.rank(talent) >= node.max_rank {
+            return Err(TalentError::MaxRank(talent.to_string()));
+        }
+
+        let required = tree.required_points(node.tier);
+        if self.spent() < required {
+            return Err(TalentError::TierLocked {
+                tier: node.tier,
+                required,
+                spent: self.spent(),
+            });
+        }
+
+        for prerequisite in &node.prerequisites {
+            if self.rank(&prerequisite.talent) < prerequisite.rank {
+                return Err(TalentError::MissingPrerequisite {
+                    talent: talent.to_string(),
+                    prerequisite: prerequisite.talent.clone(),
+                });
+            }
+        }
+
+        for (other, _) in self.ranks.iter().filter(|(_, rank)| **rank > 0) {
+            let excluded = node.exclusive_with.contains(other)
+                || tree
+                    .node(other)
+                    .is_some_and(|other| other.exclusive_with.iter().any(|id| id == talent));
+
+            if excluded {
+                return Err(TalentError::MutuallyExclusive {
+                    talent: talent.to_string(),
+                    other: other.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Puts one point into the talent, returns the new rank
+    pub fn allocate(&mut self, tree: &TalentTree, talent: &str) -> Result<u64, TalentError> {
+        self.can_allocate(tree, talent)?;
+
+        let rank = self.ranks.entry(talent.to_string()).or_insert(0);
+        *rank += 1;
+
+        Ok(*rank)
+    }
+
+    /// Refunds every spent point if `currency` covers the cost, returns the cost to deduct
+    pub fn respec(&mut self, cost: &RespecCost, currency: u64) -> Result<u64, TalentError> {
+        let cost = cost.cost(self.respecs);
+
+        if cost > currency {
+            return Err(TalentError::InsufficientFunds {
+                cost,
+                available: currency,
+            });
+        }
+
+        self.ranks.clear();
+        self.respecs += 1;
+
+        Ok(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::ExperienceSystem;
+    use crate::talent::{RespecCost, TalentAllocation, TalentError, TalentNode, TalentTree};
+
+    fn new_talent_tree() -> TalentTree {
+        TalentTree::new(
+            "Fire",
+            2,
+            vec![
+                TalentNode::new("ignite", 0, 2),
+                TalentNode::new("scorch", 0, 1),
+                TalentNode::new("fireball", 1, 1).requires("ignite", 2),
+                TalentNode::new("inferno", 2, 1).exclusive_with("meteor"),
+                TalentNode::new("meteor", 2, 1),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_allocate() {
+        let tree = new_talent_tree();
+        let mut allocation = TalentAllocation::new(3);
+
+        assert_eq!(Ok(1), allocation.allocate(&tree, "ignite"));
+        assert_eq!(Ok(2), allocation.allocate(&tree, "ignite"));
+        assert_eq!(
+            Err(TalentError::MaxRank(String::from("ignite"))),
+            allocation.allocate(&tree, "ignite")
+        );
+        assert_eq!(
+            Err(TalentError::UnknownTalent(String::from("frostbolt"))),
+            allocation.allocate(&tree, "frostbolt")
+        );
+
+        assert_eq!(Ok(1), allocation.allocate(&tree, "fireball"));
+        assert_eq!(0, allocation.available());
+        assert_eq!(
+            Err(TalentError::NoPointsAvailable),
+            allocation.allocate(&tree, "scorch")
+        );
+    }
+
+    #[test]
+    fn test_tiers_and_prerequisites() {
+        let tree = new_talent_tree();
+        let mut allocation = TalentAllocation::new(10);
+
+        assert_eq!(
+            Err(TalentError::TierLocked {
+                tier: 1,
+                required: 2,
+                spent: 0
+            }),
+            allocation.allocate(&tree, "fireball")
+        );
+
+        allocation.allocate(&tree, "ignite").unwrap();
+        allocation.allocate(&tree, "scorch").unwrap();
+        assert_eq!(
+            Err(TalentError::MissingPrerequisite {
+                talent: String::from("fireball"),
+                prerequisite: String::from("ignite")
+            }),
+            allocation.allocate(&tree, "fireball")
+        );
+    }
+
+    #[test]
+    fn test_mutually_exclusive() {
+        let tree = new_talent_tree();
+        let mut allocation = TalentAllocation::new(10);
+
+        allocation.allocate(&tree, "ignite").unwrap();
+        allocation.allocate(&tree, "ignite").unwrap();
+        allocation.allocate(&tree, "scorch").unwrap();
+        allocation.allocate(&tree, "fireball").unwrap();
+
+        allocation.allocate(&tree, "meteor").unwrap();
+        assert_eq!(
+            Err(TalentError::MutuallyExclusive {
+                talent: String::from("inferno"),
+                other: String::from("meteor")
+            }),
+            allocation.allocate(&tree, "inferno")
+        );
+    }
+
+    #[test]
+    fn test_respec() {
+        let tree = new_talent_tree();
+        let mut allocation = TalentAllocation::new(3);
+        let cost = RespecCost::Escalating {
+            base: 10,
+            increase: 40,
+            max: 60,
+        };
+
+        allocation.allocate(&tree, "ignite").unwrap();
+        assert_eq!(Ok(10), allocation.respec(&cost, 10));
+        assert_eq!(0, allocation.spent());
+        assert_eq!(3, allocation.available());
+
+        assert_eq!(
+            Err(TalentError::InsufficientFunds {
+                cost: 50,
+                available: 10
+            }),
+            allocation.respec(&cost, 10)
+        );
+        assert_eq!(Ok(50), allocation.respec(&cost, 100));
+        assert_eq!(Ok(60), allocation.respec(&cost, 100));
+        assert_eq!(3, allocation.respecs());
+    }
+
+    #[test]
+    fn test_points_from_level() {
+        let tree = new_talent_tree();
+        let mut system = ExperienceSystem::simple(0, vec![100, 250, 500]);
+
+        let mut allocation = TalentAllocation::for_level(&tree, &system);
+        assert_eq!(1, allocation.points());
+
+        system.add_experience(250);
+        allocation.update_points(&tree, &system);
+        assert_eq!(3, allocation.points());
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TalentError {
+    /// The talent does not exist in the tree
+    UnknownTalent(String),
+    /// The tree contains the same talent more than once
+    DuplicateTalent(String),
+    /// The talent has a max rank of zero
+    InvalidMaxRank(String),
+    /// There are no unspent talent points left
+    NoPointsAvailable,
+    /// The talent is already at its max rank
+    MaxRank(String),
+    /// Not enough points have been spent in the tree to unlock the tier
+    TierLocked {
+        tier: u64,
+        required: u64,
+        spent: u64,
+    },
+    /// A prerequisite has not reached the required rank
+    MissingPrerequisite {
+        talent: String,
+        prerequisite: String,
+    },
+    /// A mutually exclusive talent has already been taken
+    MutuallyExclusive { talent: String, other: String },
+    /// Not enough currency to pay for the respec
+    InsufficientFunds { cost: u64, available: u64 },
+}
+
+impl Display for TalentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TalentError::UnknownTalent(talent) => write!(f, "Unknown talent '{}'", talent),
+            TalentError::DuplicateTalent(talent) => {
+                write!(f, "Talent '{}' is defined more than once", talent)
+            }
+            TalentError::InvalidMaxRank(talent) => {
+                write!(f, "Talent '{}' must have a max rank of at least 1", talent)
+            }
+            TalentError::NoPointsAvailable => write!(f, "No talent points available"),
+            TalentError::MaxRank(talent) => write!(f, "Talent '{}' is at max rank", talent),
+            TalentError::TierLocked {
+                tier,
+                required,
+                spent,
+            } => write!(
+                f,
+                "Tier {} requires {} points spent in the tree, {} spent",
+                tier, required, spent
+            ),
+            TalentError::MissingPrerequisite {
+                talent,
+                prerequisite,
+            } => write!(f, "Talent '{}' requires '{}'", talent, prerequisite),
+            TalentError::MutuallyExclusive { talent, other } => write!(
+                f,
+                "Talent '{}' cannot be taken together with '{}'",
+                talent, other
+            ),
+            TalentError::InsufficientFunds { cost, available } => write!(
+                f,
+                "Respec costs {} but only {} is available",
+                cost, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TalentError {}
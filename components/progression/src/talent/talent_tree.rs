@@ -0,0 +1,180 @@
+use crate::talent::TalentError;
+use std::collections::BTreeSet;
+
+/// Another talent that must be at least `rank` before a talent can be taken
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalentPrerequisite {
+    pub talent: String,
+    pub rank: u64,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalentNode {
+    pub id: String,
+    /// Tiers start at 0, every tier requires more points spent in the tree
+    pub tier: u64,
+    pub max_rank: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prerequisites: Vec<TalentPrerequisite>,
+    /// Talents that cannot be taken if this talent is taken, and vice versa
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exclusive_with: Vec<String>,
+}
+
+impl TalentNode {
+    pub fn new(id: &str, tier: u64, max_rank: u64) -> Self {
+        Self {
+            id: id.to_string(),
+            tier,
+            max_rank,
+            prerequisites: vec![],
+            exclusive_with: vec![],
+        }
+    }
+
+    pub fn requires(mut self, talent: &str, rank: u64) -> Self {
+        self.prerequisites.push(TalentPrerequisite {
+            talent: talent.to_string(),
+            rank,
+        });
+        self
+    }
+
+    pub fn exclusive_with(mut self, talent: &str) -> Self {
+        self.exclusive_with.push(talent.to_string());
+        self
+    }
+}
+
+/// A tree of talents, usually loaded from a data file
+///
+/// Trees that were deserialized should be checked with `validate` before use.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalentTree {
+    pub name: String,
+    /// Points that must be spent in the tree to unlock each following tier
+    pub points_per_tier: u64,
+    /// The first level at which talent points are granted
+    pub first_talent_level: u64,
+    /// Talent points granted per level from the first talent level onwards
+    pub points_per_level: u64,
+    pub nodes: Vec<TalentNode>,
+}
+
+impl TalentTree {
+    pub fn new(name: &str, points_per_tier: u64, nodes: Vec<TalentNode>) -> Self {
+        Self {
+            name: name.to_string(),
+            points_per_tier,
+            first_talent_level: 1,
+            points_per_level: 1,
+            nodes,
+        }
+    }
+
+    pub fn node(&self, id: &str) -> Option<&TalentNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Points that must be spent in the tree before a talent of `tier` may be taken
+    pub fn required_points(&self, tier: u64) -> u64 {
+        tier.saturating_mul(self.points_per_tier)
+    }
+
+    /// Talent points granted at `level`
+    pub fn points_for_level(&self, level: u64) -> u64 {
+        if level < self.first_talent_level {
+            return 0;
+        }
+
+        (level - self.first_talent_level + 1).saturating_mul(self.points_per_level)
+    }
+
+    /// Ensures ids are unique, max ranks are valid and every referenced talent exists
+    pub fn validate(&self) -> Result<(), TalentError> {
+        let mut ids = BTreeSet::new();
+
+        for node in &self.nodes {
+            if !ids.insert(node.id.as_str()) {
+                return Err(TalentError::DuplicateTalent(node.id.clone()));
+            }
+            if node.max_rank == 0 {
+                return Err(TalentError::InvalidMaxRank(node.id.clone()));
+            }
+        }
+
+        for node in &self.nodes {
+            let referenced = node
+                .prerequisites
+                .iter()
+                .map(|prerequisite| &prerequisite.talent)
+                .chain(node.exclusive_with.iter());
+
+            for talent in referenced {
+                if !ids.contains(talent.as_str()) {
+                    return Err(TalentError::UnknownTalent(talent.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::talent::{TalentError, TalentNode, TalentTree};
+
+    #[test]
+    fn test_points_for_level() {
+        let mut tree = TalentTree::new("Test", 5, vec![]);
+        tree.first_talent_level = 10;
+
+        assert_eq!(0, tree.points_for_level(9));
+        assert_eq!(1, tree.points_for_level(10));
+        assert_eq!(51, tree.points_for_level(60));
+        assert_eq!(10, tree.required_points(2));
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = TalentTree::new(
+            "Test",
+            5,
+            vec![
+                TalentNode::new("a", 0, 5),
+                TalentNode::new("b", 1, 1).requires("a", 5),
+            ],
+        );
+        assert!(valid.validate().is_ok());
+
+        let duplicate = TalentTree::new(
+            "Test",
+            5,
+            vec![TalentNode::new("a", 0, 5), TalentNode::new("a", 0, 5)],
+        );
+        assert_eq!(
+            Err(TalentError::DuplicateTalent(String::from("a"))),
+            duplicate.validate()
+        );
+
+        let unknown = TalentTree::new(
+            "Test",
+            5,
+            vec![TalentNode::new("a", 0, 1).exclusive_with("b")],
+        );
+        assert_eq!(
+            Err(TalentError::UnknownTalent(String::from("b"))),
+            unknown.validate()
+        );
+
+        let zero = TalentTree::new("Test", 5, vec![TalentNode::new("a", 0, 0)]);
+        assert_eq!(
+            Err(TalentError::InvalidMaxRank(String::from("a"))),
+            zero.validate()
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod tier_map;
+
+pub use tier_map::{Tier, TierChange, TierMap};
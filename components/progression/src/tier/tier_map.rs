@@ -0,0 +1,263 @@
+use crate::experience::{ExperienceGain, ExperienceLoss, ExperienceSystem};
+use crate::LevelUpTable;
+use std::collections::BTreeMap;
+
+/// A named range of levels, e.g. "Journeyman" from level 10 onwards
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tier {
+    pub name: String,
+    /// The first level of the tier, the tier lasts until the next one starts
+    pub min_level: u64,
+    /// Stat bonuses granted while in this tier, they do not stack with lower tiers
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bonuses: BTreeMap<String, i64>,
+    /// Flags unlocked by reaching this tier, they stay unlocked in higher tiers
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unlocks: Vec<String>,
+}
+
+impl Tier {
+    pub fn new(name: &str, min_level: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            min_level,
+            bonuses: BTreeMap::new(),
+            unlocks: vec![],
+        }
+    }
+
+    pub fn bonus(mut self, stat: &str, value: i64) -> Self {
+        self.bonuses.insert(stat.to_string(), value);
+        self
+    }
+
+    pub fn unlock(mut self, flag: &str) -> Self {
+        self.unlocks.push(flag.to_string());
+        self
+    }
+}
+
+/// The tier before and after a level change
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierChange {
+    /// The previous tier, None when below the first tier
+    pub previous: Option<String>,
+    /// The current tier, None when below the first tier
+    pub tier: Option<String>,
+    /// Whether the new tier is higher than the previous one
+    pub promoted: bool,
+}
+
+/// Maps level ranges to named tiers, on top of any LevelUpTable
+///
+/// Levels below the first tier have no tier.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "TierData"))]
+pub struct TierMap {
+    name: String,
+    tiers: Vec<Tier>,
+}
+
+/// Deserialized maps go through `named` to restore the sorting lookups rely on
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TierData {
+    name: String,
+    tiers: Vec<Tier>,
+}
+
+#[cfg(feature = "serde")]
+impl From<TierData> for TierMap {
+    fn from(data: TierData) -> Self {
+        Self::named(data.name, data.tiers)
+    }
+}
+
+impl TierMap {
+    pub fn named(name: String, tiers: Vec<Tier>) -> Self {
+        let mut tiers = tiers;
+        tiers.sort_by_key(|tier| tier.min_level);
+
+        Self { name, tiers }
+    }
+
+    pub fn new(tiers: Vec<Tier>) -> Self {
+        Self::named(String::from("Default"), tiers)
+    }
+
+    /// The D&D 5e proficiency bonus, +2 at level 1 rising by one every four levels up to +6
+    pub fn proficiency_5e() -> Self {
+        Self::named(
+            String::from("Proficiency Bonus"),
+            (0..5)
+                .map(|step| {
+                    let bonus = step as i64 + 2;
+
+                    Tier::new(&format!("+{}", bonus), step * 4 + 1).bonus("proficiency", bonus)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn tiers(&self) -> &[Tier] {
+        &self.tiers
+    }
+
+    pub fn tier_for(&self, level: u64) -> Option<&Tier> {
+        self.index(level).map(|index| &self.tiers[index])
+    }
+
+    pub fn tier_for_experience(
+        &self,
+        table: &impl LevelUpTable,
+        experience_points: u64,
+    ) -> Option<&Tier> {
+        self.tier_for(table.to_level(experience_points))
+    }
+
+    pub fn tier_of(&self, system: &ExperienceSystem) -> Option<&Tier> {
+        self.tier_for(system.get_level())
+    }
+
+    /// The bonus to a stat at a level, 0 if the tier does not grant it
+    pub fn bonus(&self, level: u64, stat: &str) -> i64 {
+        self.tier_for(level)
+            .and_then(|tier| tier.bonuses.get(stat))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Every flag unlocked by the tiers reached at a level
+    pub fn unlocks(&self, level: u64) -> Vec<&str> {
+        self.index(level).map_or(vec![], |index| {
+            self.tiers[..=index]
+                .iter()
+                .flat_map(|tier| tier.unlocks.iter().map(String::as_str))
+                .collect()
+        })
+    }
+
+    pub fn is_unlocked(&self, level: u64, flag: &str) -> bool {
+        self.unlocks(level).contains(&flag)
+    }
+
+    /// The tier change between two levels, None if both are in the same tier
+    pub fn change(&self, previous_level: u64, level: u64) -> Option<TierChange> {
+        let previous = self.index(previous_level);
+        let current = self.index(level);
+
+        if previous == current {
+            return None;
+        }
+
+        Some(TierChange {
+            previous: previous.map(|index| self.tiers[index].name.clone()),
+            tier: current.map(|index| self.tiers[index].name.clone()),
+            promoted: current > previous,
+        })
+    }
+
+    pub fn change_for_gain(&self, gain: &ExperienceGain) -> Option<TierChange> {
+        self.change(gain.previous_level, gain.level)
+    }
+
+    pub fn change_for_loss(&self, loss: &ExperienceLoss) -> Option<TierChange> {
+        self.change(loss.previous_level, loss.level)
+    }
+
+    fn index(&self, level: u64) -> Option<usize> {
+        self.tiers
+            .partition_point(|tier| tier.min_level <= level)
+            .checked_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::experience::{ExperienceLevelUpTable, ExperienceSystem};
+    use crate::tier::{Tier, TierMap};
+
+    fn new_mastery() -> TierMap {
+        TierMap::named(
+            String::from("Mastery"),
+            vec![
+                Tier::new("Master", 40)
+                    .bonus("crafting", 20)
+                    .unlock("masterwork"),
+                Tier::new("Novice", 1),
+                Tier::new("Apprentice", 10)
+                    .bonus("crafting", 5)
+                    .unlock("recipes"),
+                Tier::new("Journeyman", 20).bonus("crafting", 10),
+                Tier::new("Expert", 30).bonus("crafting", 15),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_tier_for() {
+        let tiers = new_mastery();
+
+        assert!(tiers.tier_for(0).is_none());
+        assert_eq!("Novice", tiers.tier_for(9).unwrap().name);
+        assert_eq!("Apprentice", tiers.tier_for(10).unwrap().name);
+        assert_eq!("Master", tiers.tier_for(99).unwrap().name);
+
+        assert_eq!(0, tiers.bonus(5, "crafting"));
+        assert_eq!(15, tiers.bonus(35, "crafting"));
+    }
+
+    #[test]
+    fn test_unlocks() {
+        let tiers = new_mastery();
+
+        assert!(tiers.unlocks(5).is_empty());
+        assert!(tiers.is_unlocked(25, "recipes"));
+        assert!(!tiers.is_unlocked(25, "masterwork"));
+        assert_eq!(vec!["recipes", "masterwork"], tiers.unlocks(40));
+    }
+
+    #[test]
+    fn test_change() {
+        let tiers = TierMap::new(vec![Tier::new("Novice", 1), Tier::new("Apprentice", 3)]);
+        let mut system = ExperienceSystem::simple(0, vec![10, 20, 30]);
+
+        let gain = system.gain_experience(10);
+        assert!(tiers.change_for_gain(&gain).is_none());
+
+        let gain = system.gain_experience(10);
+        let change = tiers.change_for_gain(&gain).unwrap();
+        assert_eq!(Some(String::from("Novice")), change.previous);
+        assert_eq!(Some(String::from("Apprentice")), change.tier);
+        assert!(change.promoted);
+
+        let loss = system.lose_experience(20);
+        assert!(!tiers.change_for_loss(&loss).unwrap().promoted);
+        assert_eq!("Novice", tiers.tier_of(&system).unwrap().name);
+
+        let table = ExperienceLevelUpTable::new(vec![10, 20, 30]);
+        assert_eq!(
+            "Apprentice",
+            tiers.tier_for_experience(&table, 25).unwrap().name
+        );
+    }
+
+    #[test]
+    fn test_proficiency_5e() {
+        let proficiency = TierMap::proficiency_5e();
+
+        assert_eq!(2, proficiency.bonus(1, "proficiency"));
+        assert_eq!(2, proficiency.bonus(4, "proficiency"));
+        assert_eq!(3, proficiency.bonus(5, "proficiency"));
+        assert_eq!(5, proficiency.bonus(16, "proficiency"));
+        assert_eq!(6, proficiency.bonus(20, "proficiency"));
+        assert_eq!("+4", proficiency.tier_for(9).unwrap().name);
+    }
+}
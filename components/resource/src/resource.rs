@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter};
 use std::sync::atomic::AtomicU64;
-use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::Ordering::{Relaxed, SeqCst};
 
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -47,6 +47,24 @@ impl Resource for GameResource {
     }
 }
 
+impl GameResource {
+    /// Removes `amount` in a single compare-and-swap, so it can be shared between threads
+    ///
+    /// returns the previous value, or Err(current value) if it is less than `amount`
+    pub fn try_remove(&self, amount: u64) -> Result<u64, u64> {
+        self.resource_value
+            .fetch_update(SeqCst, SeqCst, |value| value.checked_sub(amount))
+    }
+
+    /// Adds `amount` in a single compare-and-swap, so it can be shared between threads
+    ///
+    /// returns the previous value, or Err(current value) if the value would overflow
+    pub fn try_add(&self, amount: u64) -> Result<u64, u64> {
+        self.resource_value
+            .fetch_update(SeqCst, SeqCst, |value| value.checked_add(amount))
+    }
+}
+
 impl Clone for GameResource {
     fn clone(&self) -> Self {
         Self {
@@ -98,4 +116,15 @@ mod tests {
         let mut resource = GameResource::new("Test Resource".to_string(), 0);
         resource.remove(100).unwrap();
     }
+
+    #[test]
+    fn test_resource_shared() {
+        let resource = GameResource::new("Test Resource".to_string(), 100);
+
+        assert_eq!(Ok(100), resource.try_remove(60));
+        assert_eq!(Err(40), resource.try_remove(41));
+        assert_eq!(Ok(40), resource.try_add(10));
+        assert_eq!(Err(50), resource.try_add(u64::MAX));
+        assert_eq!(resource.value(), 50);
+    }
 }
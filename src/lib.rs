@@ -1,7 +1,15 @@
+#[cfg(feature = "automation")]
+pub use overworld_incremental::automation;
 #[cfg(feature = "counter")]
 pub use overworld_incremental::counter;
+#[cfg(feature = "number")]
+pub use overworld_incremental::number;
 #[cfg(feature = "progress")]
 pub use overworld_incremental::progress;
+#[cfg(feature = "simulation")]
+pub use overworld_incremental::simulation;
+#[cfg(feature = "upgrade")]
+pub use overworld_incremental::upgrade;
 
 #[cfg(feature = "resource")]
 pub use overworld_resource::resource;
@@ -16,3 +24,6 @@ pub use overworld_dice::roll;
 
 #[cfg(feature = "affinity")]
 pub use overworld_affinity as affinity;
+
+#[cfg(feature = "achievement")]
+pub use overworld_achievement as achievement;